@@ -0,0 +1,47 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// Every `src/dayX.rs` is a solution module. Declaring them here means adding a day
+// only needs the new file, which registers itself through its `SOLUTION` constant.
+fn main() {
+  println!("cargo:rerun-if-changed=src");
+
+  let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+  let src_dir = Path::new(&manifest_dir).join("src");
+
+  let mut days = fs::read_dir(&src_dir)
+    .expect("Could not read src directory")
+    .filter_map(|entry| {
+      let name = entry.ok()?.file_name().into_string().ok()?;
+      name
+        .strip_prefix("day")?
+        .strip_suffix(".rs")?
+        .parse::<u8>()
+        .ok()
+    })
+    .collect::<Vec<u8>>();
+  days.sort_unstable();
+
+  let mut output = String::new();
+  for day in &days {
+    let path = src_dir.join(format!("day{day}.rs"));
+    writeln!(output, "#[path = {:?}]", path.display().to_string()).unwrap();
+    writeln!(output, "pub mod day{day};").unwrap();
+  }
+
+  let solutions = days
+    .iter()
+    .map(|day| format!("day{day}::SOLUTION"))
+    .collect::<Vec<String>>()
+    .join(", ");
+  writeln!(
+    output,
+    "pub const SOLUTIONS: &[registry::Solution] = &[{solutions}];"
+  )
+  .unwrap();
+
+  let out_dir = env::var("OUT_DIR").expect("OUT_DIR is not set");
+  fs::write(Path::new(&out_dir).join("solutions.rs"), output).expect("Could not write solutions");
+}
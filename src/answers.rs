@@ -0,0 +1,272 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const ANSWERS: &str = "answers.toml";
+
+/// What the puzzle said about a rejected answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hint {
+  TooHigh,
+  TooLow,
+}
+
+impl fmt::Display for Hint {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Hint::TooHigh => write!(f, "too high"),
+      Hint::TooLow => write!(f, "too low"),
+    }
+  }
+}
+
+/// An answer that was submitted and rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejection {
+  pub answer: String,
+  pub hint: Option<Hint>,
+}
+
+/// Everything known about the answer to one part of one input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+  pub part: u8,
+  /// The input's file, relative to the day's input directory.
+  pub input: String,
+  pub accepted: Option<String>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub rejected: Vec<Rejection>,
+}
+
+/// How an answer compares to what's already known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+  /// Nothing is known about this part and input yet.
+  Unknown,
+  Correct,
+  /// The answer doesn't match the one that was accepted.
+  Regressed {
+    accepted: String,
+  },
+  /// The answer was already rejected, or is beyond an answer rejected as too high or too low.
+  Wrong {
+    rejected: String,
+    hint: Option<Hint>,
+  },
+  /// The answer hasn't been tried, and nothing rules it out.
+  Untried,
+}
+
+/// The answers recorded for a day, read from `input/dayN/answers.toml`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+  #[serde(rename = "answer", default)]
+  pub entries: Vec<Entry>,
+}
+
+impl Answers {
+  pub fn parse(contents: &str) -> Result<Self, String> {
+    toml::from_str(contents).map_err(|err| err.to_string())
+  }
+
+  /// Reads the answers in `day_dir`, or none if it doesn't have any yet.
+  pub fn load(day_dir: &Path) -> Result<Self, String> {
+    let path = day_dir.join(ANSWERS);
+    if !path.exists() {
+      return Ok(Answers::default());
+    }
+
+    let contents = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+    Answers::parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+  }
+
+  pub fn save(&self, day_dir: &Path) -> io::Result<()> {
+    let contents = toml::to_string_pretty(self).map_err(io::Error::other)?;
+    fs::write(day_dir.join(ANSWERS), contents)
+  }
+
+  pub fn get(&self, part: u8, input: &str) -> Option<&Entry> {
+    self
+      .entries
+      .iter()
+      .find(|entry| entry.part == part && entry.input == input)
+  }
+
+  fn entry(&mut self, part: u8, input: &str) -> &mut Entry {
+    let idx = self
+      .entries
+      .iter()
+      .position(|entry| entry.part == part && entry.input == input)
+      .unwrap_or_else(|| {
+        self.entries.push(Entry {
+          part,
+          input: input.to_string(),
+          accepted: None,
+          rejected: Vec::new(),
+        });
+        self.entries.len() - 1
+      });
+    &mut self.entries[idx]
+  }
+
+  pub fn accepted(&self, part: u8, input: &str) -> Option<&str> {
+    self.get(part, input)?.accepted.as_deref()
+  }
+
+  pub fn accept(&mut self, part: u8, input: &str, answer: &str) {
+    self.entry(part, input).accepted = Some(answer.to_string());
+  }
+
+  pub fn reject(&mut self, part: u8, input: &str, answer: &str, hint: Option<Hint>) {
+    let entry = self.entry(part, input);
+    entry
+      .rejected
+      .retain(|rejection| rejection.answer != answer);
+    entry.rejected.push(Rejection {
+      answer: answer.to_string(),
+      hint,
+    });
+  }
+
+  pub fn check(&self, part: u8, input: &str, answer: &str) -> Verdict {
+    let Some(entry) = self.get(part, input) else {
+      return Verdict::Unknown;
+    };
+
+    if let Some(accepted) = &entry.accepted {
+      return if accepted == answer {
+        Verdict::Correct
+      } else {
+        Verdict::Regressed {
+          accepted: accepted.clone(),
+        }
+      };
+    }
+
+    let wrong = |rejection: &Rejection| Verdict::Wrong {
+      rejected: rejection.answer.clone(),
+      hint: rejection.hint,
+    };
+
+    if let Some(rejection) = entry.rejected.iter().find(|r| r.answer == answer) {
+      return wrong(rejection);
+    }
+
+    // An answer past one that was too high or too low is wrong too
+    let Ok(value) = answer.parse::<i128>() else {
+      return Verdict::Untried;
+    };
+    entry
+      .rejected
+      .iter()
+      .find(
+        |rejection| match (rejection.answer.parse::<i128>(), rejection.hint) {
+          (Ok(rejected), Some(Hint::TooHigh)) => value >= rejected,
+          (Ok(rejected), Some(Hint::TooLow)) => value <= rejected,
+          _ => false,
+        },
+      )
+      .map_or(Verdict::Untried, wrong)
+  }
+}
+
+/// The name answers are recorded under for `input`, if it's in `day_dir`. Other inputs, like ones
+/// given with `--input`, aren't tracked.
+pub fn input_key(day_dir: &Path, input: &str) -> Option<String> {
+  Path::new(input)
+    .strip_prefix(day_dir)
+    .ok()
+    .map(|path| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn answers() -> Answers {
+    Answers::parse(
+      r#"
+        [[answer]]
+        part = 1
+        input = "part1.txt"
+        accepted = "1882714"
+
+        [[answer]]
+        part = 2
+        input = "part1.txt"
+        rejected = [
+          { answer = "500", hint = "too-low" },
+          { answer = "900", hint = "too-high" },
+          { answer = "abc" },
+        ]
+      "#,
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn test_check() {
+    let answers = answers();
+    assert_eq!(answers.check(1, "part1.txt", "1882714"), Verdict::Correct);
+    assert_eq!(
+      answers.check(1, "part1.txt", "1882715"),
+      Verdict::Regressed {
+        accepted: "1882714".to_string()
+      }
+    );
+    assert_eq!(answers.check(1, "part2.txt", "1"), Verdict::Unknown);
+    assert_eq!(answers.check(2, "part1.txt", "700"), Verdict::Untried);
+    assert_eq!(
+      answers.check(2, "part1.txt", "abc"),
+      Verdict::Wrong {
+        rejected: "abc".to_string(),
+        hint: None
+      }
+    );
+    assert_eq!(
+      answers.check(2, "part1.txt", "1000"),
+      Verdict::Wrong {
+        rejected: "900".to_string(),
+        hint: Some(Hint::TooHigh)
+      }
+    );
+    assert_eq!(
+      answers.check(2, "part1.txt", "42"),
+      Verdict::Wrong {
+        rejected: "500".to_string(),
+        hint: Some(Hint::TooLow)
+      }
+    );
+  }
+
+  #[test]
+  fn test_record() {
+    let mut answers = answers();
+    answers.reject(2, "part1.txt", "500", None);
+    answers.accept(2, "part1.txt", "777");
+    answers.accept(1, "part2.txt", "1");
+
+    let entry = answers.get(2, "part1.txt").unwrap();
+    assert_eq!(entry.accepted.as_deref(), Some("777"));
+    assert_eq!(entry.rejected.len(), 3);
+    assert_eq!(entry.rejected[2].hint, None);
+    assert_eq!(answers.entries.len(), 3);
+
+    let saved = toml::to_string_pretty(&answers).unwrap();
+    assert_eq!(Answers::parse(&saved), Ok(answers));
+  }
+
+  #[test]
+  fn test_input_key() {
+    let day_dir = Path::new("input/day1");
+    assert_eq!(
+      input_key(day_dir, "input/day1/part2.txt").as_deref(),
+      Some("part2.txt")
+    );
+    assert_eq!(input_key(day_dir, "/tmp/other.txt"), None);
+  }
+}
@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::error::SolveError;
+
+/// How many times to run each part.
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+  pub warmup: u32,
+  pub runs: u32,
+  /// Keep running until this much time has been spent, instead of a fixed number of runs.
+  pub budget: Option<Duration>,
+}
+
+/// Summary statistics over a set of timings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+  pub runs: usize,
+  pub min: Duration,
+  pub median: Duration,
+  pub mean: Duration,
+  pub p95: Duration,
+  pub stddev: Duration,
+}
+
+impl Stats {
+  pub fn from_samples(samples: &[Duration]) -> Self {
+    assert!(!samples.is_empty(), "Need at least one sample");
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = sorted.len() as f64;
+    let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+    let variance = sorted
+      .iter()
+      .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+      .sum::<f64>()
+      / count;
+
+    // Nearest rank, so p95 is always one of the samples
+    #[allow(
+      clippy::cast_possible_truncation,
+      clippy::cast_sign_loss,
+      clippy::cast_precision_loss
+    )]
+    let p95_idx = ((0.95 * count).ceil() as usize).saturating_sub(1);
+
+    Stats {
+      runs: sorted.len(),
+      min: sorted[0],
+      median: sorted[sorted.len() / 2],
+      mean: Duration::from_secs_f64(mean),
+      p95: sorted[p95_idx],
+      stddev: Duration::from_secs_f64(variance.sqrt()),
+    }
+  }
+}
+
+/// Benchmark results for one part of one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartBench {
+  pub day: u8,
+  pub part: u8,
+  pub parse: Stats,
+  pub solve: Stats,
+}
+
+impl PartBench {
+  /// The typical time for the whole part, parsing included.
+  pub fn total(&self) -> Duration {
+    self.parse.median + self.solve.median
+  }
+}
+
+/// Runs `run` repeatedly according to `options`. `run` returns how long parsing and solving took.
+pub fn sample(
+  day: u8,
+  part: u8,
+  options: &Options,
+  mut run: impl FnMut() -> Result<(Duration, Duration), SolveError>,
+) -> Result<PartBench, SolveError> {
+  for _ in 0..options.warmup {
+    run()?;
+  }
+
+  let mut parse_samples = Vec::new();
+  let mut solve_samples = Vec::new();
+  let start = Instant::now();
+
+  loop {
+    let (parse, solve) = run()?;
+    parse_samples.push(parse);
+    solve_samples.push(solve);
+
+    let done = match options.budget {
+      Some(budget) => start.elapsed() >= budget,
+      None => solve_samples.len() >= options.runs as usize,
+    };
+    if done {
+      break;
+    }
+  }
+
+  Ok(PartBench {
+    day,
+    part,
+    parse: Stats::from_samples(&parse_samples),
+    solve: Stats::from_samples(&solve_samples),
+  })
+}
+
+pub fn render(results: &[PartBench]) -> String {
+  let mut output = String::new();
+  writeln!(
+    output,
+    "{:<4} {:<4} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+    "day", "part", "runs", "parse", "min", "median", "mean", "p95", "stddev"
+  )
+  .unwrap();
+
+  for result in results {
+    let solve = &result.solve;
+    writeln!(
+      output,
+      "{:<4} {:<4} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+      result.day,
+      result.part,
+      solve.runs,
+      format!("{:.2?}", result.parse.median),
+      format!("{:.2?}", solve.min),
+      format!("{:.2?}", solve.median),
+      format!("{:.2?}", solve.mean),
+      format!("{:.2?}", solve.p95),
+      format!("{:.2?}", solve.stddev),
+    )
+    .unwrap();
+  }
+
+  output
+}
+
+pub fn save_baseline(path: &Path, results: &[PartBench]) -> std::io::Result<()> {
+  let json = serde_json::to_string_pretty(results).expect("Results are always serializable");
+  fs::write(path, json)
+}
+
+pub fn load_baseline(path: &Path) -> std::io::Result<Vec<PartBench>> {
+  let json = fs::read_to_string(path)?;
+  serde_json::from_str(&json).map_err(std::io::Error::other)
+}
+
+/// How a part's median time changed against the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+  pub day: u8,
+  pub part: u8,
+  pub before: Duration,
+  pub after: Duration,
+  /// The relative change in percent, positive when slower.
+  pub change: f64,
+  pub regressed: bool,
+}
+
+/// Compares the parts benchmarked in both runs. A part regresses if it got more than `threshold`
+/// percent slower. Any time at all is an infinite slowdown from a baseline of 0.
+pub fn compare(baseline: &[PartBench], current: &[PartBench], threshold: f64) -> Vec<Comparison> {
+  current
+    .iter()
+    .filter_map(|after| {
+      let before = baseline
+        .iter()
+        .find(|before| before.day == after.day && before.part == after.part)?;
+      let (before, after_total) = (before.total(), after.total());
+      let change = if before.is_zero() {
+        if after_total.is_zero() {
+          0.0
+        } else {
+          f64::INFINITY
+        }
+      } else {
+        (after_total.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0
+      };
+
+      Some(Comparison {
+        day: after.day,
+        part: after.part,
+        before,
+        after: after_total,
+        change,
+        regressed: change > threshold,
+      })
+    })
+    .collect()
+}
+
+pub fn render_comparison(comparisons: &[Comparison]) -> String {
+  let mut output = String::new();
+  for comparison in comparisons {
+    writeln!(
+      output,
+      "day{} Part {}: {:.2?} -> {:.2?} ({:+.1}%){}",
+      comparison.day,
+      comparison.part,
+      comparison.before,
+      comparison.after,
+      comparison.change,
+      if comparison.regressed {
+        " REGRESSION"
+      } else {
+        ""
+      }
+    )
+    .unwrap();
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn millis(values: &[u64]) -> Vec<Duration> {
+    values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+  }
+
+  fn bench(day: u8, part: u8, solve_ms: u64) -> PartBench {
+    PartBench {
+      day,
+      part,
+      parse: Stats::from_samples(&millis(&[0])),
+      solve: Stats::from_samples(&millis(&[solve_ms])),
+    }
+  }
+
+  #[test]
+  fn test_stats() {
+    let stats = Stats::from_samples(&millis(&[5, 1, 4, 2, 3]));
+    assert_eq!(stats.runs, 5);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.mean, Duration::from_millis(3));
+    assert_eq!(stats.p95, Duration::from_millis(5));
+    assert_eq!(stats.stddev.as_micros(), 1414);
+  }
+
+  #[test]
+  fn test_sample_runs() {
+    let options = Options {
+      warmup: 2,
+      runs: 4,
+      budget: None,
+    };
+    let mut calls = 0;
+    let result = sample(1, 1, &options, || {
+      calls += 1;
+      Ok((Duration::ZERO, Duration::from_millis(1)))
+    })
+    .unwrap();
+
+    assert_eq!(calls, 6);
+    assert_eq!(result.solve.runs, 4);
+  }
+
+  #[test]
+  fn test_sample_error() {
+    let options = Options {
+      warmup: 0,
+      runs: 4,
+      budget: None,
+    };
+    let result = sample(1, 1, &options, || Err(SolveError::NotImplemented));
+    assert_eq!(result, Err(SolveError::NotImplemented));
+  }
+
+  #[test]
+  fn test_compare() {
+    let baseline = vec![bench(22, 1, 100), bench(22, 2, 4800)];
+    let current = vec![bench(22, 1, 105), bench(22, 2, 6000), bench(23, 1, 10)];
+
+    let comparisons = compare(&baseline, &current, 10.0);
+    assert_eq!(comparisons.len(), 2);
+    assert!(!comparisons[0].regressed);
+    assert!(comparisons[1].regressed);
+    assert!((comparisons[1].change - 25.0).abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_compare_zero_baseline() {
+    let baseline = vec![bench(22, 1, 0), bench(22, 2, 0)];
+    let current = vec![bench(22, 1, 0), bench(22, 2, 10)];
+
+    let comparisons = compare(&baseline, &current, 10.0);
+    assert_eq!(comparisons[0].change, 0.0);
+    assert!(!comparisons[0].regressed);
+    assert_eq!(comparisons[1].change, f64::INFINITY);
+    assert!(comparisons[1].regressed);
+  }
+}
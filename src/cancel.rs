@@ -0,0 +1,159 @@
+//! Lets the runner give up on a part that's taking too long.
+//!
+//! Each part solved with a timeout runs on its own thread with a [`CancelToken`], which is
+//! cancelled when the time is up. The runner moves on either way, but a solver that polls [`check`]
+//! in its long loops stops too, rather than spinning in the background.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::SolveError;
+
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+  pub fn cancel(&self) {
+    self.0.store(true, Ordering::Relaxed);
+  }
+
+  pub fn is_cancelled(&self) -> bool {
+    self.0.load(Ordering::Relaxed)
+  }
+
+  /// Errors once the token has been cancelled, so a solver can bail out with `?`.
+  pub fn check(&self) -> Result<(), SolveError> {
+    if self.is_cancelled() {
+      Err(SolveError::Cancelled)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+thread_local! {
+  static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+/// The token for the part being solved on this thread. Parts solved without a timeout get one
+/// that's never cancelled.
+pub fn current() -> CancelToken {
+  CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Errors once the part being solved on this thread has been cancelled. Solvers with loops that
+/// could run for a long time should call this every so often.
+pub fn check() -> Result<(), SolveError> {
+  CURRENT.with(|current| match &*current.borrow() {
+    Some(token) => token.check(),
+    None => Ok(()),
+  })
+}
+
+/// Runs `f` with `token` as this thread's current token.
+pub fn with_token<T>(token: CancelToken, f: impl FnOnce() -> T) -> T {
+  let previous = CURRENT.with(|current| current.replace(Some(token)));
+  let result = f();
+  CURRENT.with(|current| *current.borrow_mut() = previous);
+  result
+}
+
+/// Runs `f` on its own thread, giving up on it and cancelling its token if it hasn't finished
+/// within `limit`.
+pub fn run_with_timeout<T: Send + 'static>(
+  limit: Duration,
+  f: impl FnOnce() -> Result<T, SolveError> + Send + 'static,
+) -> Result<T, SolveError> {
+  let token = CancelToken::default();
+  let (sender, receiver) = mpsc::channel();
+
+  let thread_token = token.clone();
+  thread::spawn(move || {
+    let result = with_token(thread_token, f);
+    // Nobody is listening if it timed out
+    let _ = sender.send(result);
+  });
+
+  match receiver.recv_timeout(limit) {
+    Ok(result) => result,
+    Err(mpsc::RecvTimeoutError::Timeout) => {
+      token.cancel();
+      Err(SolveError::TimedOut(limit))
+    }
+    Err(mpsc::RecvTimeoutError::Disconnected) => Err(SolveError::Panicked(
+      "the solver's thread stopped".to_string(),
+    )),
+  }
+}
+
+/// A time limit from `--timeout`, for every part, a day, or one part of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeout {
+  pub day: Option<u8>,
+  pub part: Option<u8>,
+  pub limit: Duration,
+}
+
+impl Timeout {
+  fn applies(&self, day: u8, part: u8) -> bool {
+    self.day.is_none_or(|d| d == day) && self.part.is_none_or(|p| p == part)
+  }
+}
+
+/// The limit for `part` of `day`, from the most specific of `timeouts` that applies to it.
+pub fn limit(timeouts: &[Timeout], day: u8, part: u8) -> Option<Duration> {
+  timeouts
+    .iter()
+    .filter(|timeout| timeout.applies(day, part))
+    .max_by_key(|timeout| (timeout.day.is_some(), timeout.part.is_some()))
+    .map(|timeout| timeout.limit)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_run_with_timeout() {
+    assert_eq!(run_with_timeout(Duration::from_secs(10), || Ok(42)), Ok(42));
+
+    let (sender, receiver) = mpsc::channel();
+    let result = run_with_timeout(Duration::from_millis(50), move || {
+      let token = current();
+      while !token.is_cancelled() {
+        thread::sleep(Duration::from_millis(5));
+      }
+      sender.send(check()).unwrap();
+      Ok(0)
+    });
+    assert_eq!(result, Err(SolveError::TimedOut(Duration::from_millis(50))));
+    // The solver saw it was cancelled and stopped
+    assert_eq!(
+      receiver.recv_timeout(Duration::from_secs(10)),
+      Ok(Err(SolveError::Cancelled))
+    );
+    assert_eq!(check(), Ok(()));
+  }
+
+  #[test]
+  fn test_limit() {
+    let timeout = |day, part, secs| Timeout {
+      day,
+      part,
+      limit: Duration::from_secs(secs),
+    };
+    let timeouts = [
+      timeout(Some(14), Some(2), 60),
+      timeout(None, None, 10),
+      timeout(Some(14), None, 30),
+    ];
+
+    assert_eq!(limit(&timeouts, 1, 1), Some(Duration::from_secs(10)));
+    assert_eq!(limit(&timeouts, 14, 1), Some(Duration::from_secs(30)));
+    assert_eq!(limit(&timeouts, 14, 2), Some(Duration::from_secs(60)));
+    assert_eq!(limit(&timeouts[2..], 1, 1), None);
+  }
+}
@@ -0,0 +1,634 @@
+use clap::{ArgAction, ArgGroup, Args, Parser, Subcommand};
+use std::collections::BTreeSet;
+use std::env;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::answers::Hint;
+use crate::cancel::{self, Timeout};
+use crate::fetch;
+use crate::params::Params;
+use crate::registry;
+use crate::report::Format;
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code 2024, in Rust.")]
+pub struct Cli {
+  #[command(subcommand)]
+  pub command: Option<Command>,
+
+  #[command(flatten)]
+  pub log: LogArgs,
+}
+
+#[derive(Debug, Default, Clone, Args)]
+pub struct LogArgs {
+  /// Log what the solvers are doing, `-v` for debug and `-vv` for everything
+  #[arg(short, long, action = ArgAction::Count, global = true)]
+  pub verbose: u8,
+
+  /// Log everything from this day, e.g. `--trace day15`
+  #[arg(long, value_name = "DAY", value_parser = parse_trace_day, global = true)]
+  pub trace: Vec<u8>,
+
+  /// Write the log to FILE instead of stderr
+  #[arg(long, value_name = "FILE", global = true)]
+  pub log_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+  /// Check each part against its example, then solve the real input (the default)
+  Check(CheckArgs),
+  /// Solve the real input without checking the examples first
+  Run(RunArgs),
+  /// Time each part against the real input
+  Bench(BenchArgs),
+  /// List the available days
+  List,
+  /// Start a new day from the `src/dayN.rs` template
+  New(NewArgs),
+  /// Check a day against its examples again whenever its source or inputs change
+  Watch(WatchArgs),
+  /// Record whether an answer was accepted or rejected
+  Answer(AnswerArgs),
+  /// Solve a day's real input and submit the answer to the puzzle site
+  Submit(SubmitArgs),
+  /// Download the real inputs that are missing
+  Fetch(FetchArgs),
+  /// Write out the examples from a saved puzzle page
+  Extract(ExtractArgs),
+}
+
+#[derive(Debug, Default, Clone, Args)]
+pub struct RunArgs {
+  #[command(flatten)]
+  pub selection: Selection,
+
+  /// How to report the results once every day has finished
+  #[arg(long, value_enum, default_value_t)]
+  pub format: Format,
+
+  /// How many days to solve at once. Defaults to one per core
+  #[arg(long, value_name = "N")]
+  pub jobs: Option<NonZeroUsize>,
+
+  /// Solve one day at a time, the same as `--jobs 1`
+  #[arg(long, conflicts_with = "jobs")]
+  pub sequential: bool,
+
+  /// Print each day's progress as soon as it finishes, rather than in day order
+  #[arg(long)]
+  pub stream: bool,
+}
+
+impl RunArgs {
+  /// How many threads to solve days on, if not the default.
+  pub fn jobs(&self) -> Option<usize> {
+    if self.sequential {
+      Some(1)
+    } else {
+      self.jobs.map(NonZeroUsize::get)
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone, Args)]
+pub struct CheckArgs {
+  #[command(flatten)]
+  pub run: RunArgs,
+
+  /// Stop after the examples, without solving the real input
+  #[arg(long)]
+  pub examples_only: bool,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct BenchArgs {
+  #[command(flatten)]
+  pub selection: Selection,
+
+  /// Untimed runs of each part before measuring
+  #[arg(long, default_value_t = 1)]
+  pub warmup: u32,
+
+  /// Timed runs of each part
+  #[arg(
+    long,
+    default_value_t = 10,
+    conflicts_with = "budget",
+    value_parser = clap::value_parser!(u32).range(1..)
+  )]
+  pub runs: u32,
+
+  /// Keep running each part for this many seconds, instead of a fixed number of runs
+  #[arg(long, value_parser = parse_seconds)]
+  pub budget: Option<Duration>,
+
+  /// Save the results to this file, to compare later runs against
+  #[arg(long)]
+  pub save_baseline: Option<PathBuf>,
+
+  /// Compare the results against a file saved with `--save-baseline`
+  #[arg(long)]
+  pub baseline: Option<PathBuf>,
+
+  /// How many percent slower a part has to get to count as a regression
+  #[arg(long, default_value_t = 10.0)]
+  pub threshold: f64,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct NewArgs {
+  /// The day to create
+  #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+  pub day: u8,
+
+  /// The year the day's puzzle is from
+  #[arg(long, default_value_t = registry::YEAR)]
+  pub year: u16,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct WatchArgs {
+  /// The day to watch
+  #[arg(long)]
+  pub day: u8,
+
+  /// Only check this part
+  #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  pub part: Option<u8>,
+
+  /// Also solve the real input once the examples pass
+  #[arg(long)]
+  pub real: bool,
+
+  /// How often to look for changes, in milliseconds
+  #[arg(long, default_value_t = 500)]
+  pub interval: u64,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+#[command(group(ArgGroup::new("result").required(true).args(["accept", "reject"])))]
+pub struct AnswerArgs {
+  #[arg(long)]
+  pub day: u8,
+
+  #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  pub part: u8,
+
+  /// The input the answer is for, relative to the day's input directory. Defaults to the part's
+  /// real input
+  #[arg(long)]
+  pub input: Option<String>,
+
+  /// Record ANSWER as the accepted answer
+  #[arg(long, value_name = "ANSWER")]
+  pub accept: Option<String>,
+
+  /// Record ANSWER as a rejected answer
+  #[arg(long, value_name = "ANSWER")]
+  pub reject: Option<String>,
+
+  /// Whether the rejected answer was too high or too low
+  #[arg(long, value_enum, conflicts_with = "accept")]
+  pub hint: Option<Hint>,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct SubmitArgs {
+  #[arg(long)]
+  pub day: u8,
+
+  #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  pub part: u8,
+
+  #[arg(long, default_value_t = registry::YEAR)]
+  pub year: u16,
+
+  /// The site to submit to. Defaults to `$AOC_URL`, or adventofcode.com
+  #[arg(long)]
+  pub url: Option<String>,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct FetchArgs {
+  /// Only fetch this day's input. Defaults to every day with a solution
+  #[arg(long)]
+  pub day: Option<u8>,
+
+  #[arg(long, default_value_t = registry::YEAR)]
+  pub year: u16,
+
+  /// The site to fetch from. Defaults to `$AOC_URL`, or adventofcode.com
+  #[arg(long)]
+  pub url: Option<String>,
+
+  /// How long to wait between downloads, in seconds
+  #[arg(long, default_value_t = fetch::DEFAULT_THROTTLE.as_secs())]
+  pub throttle: u64,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct ExtractArgs {
+  #[arg(long)]
+  pub day: u8,
+
+  /// The puzzle's page, saved from the browser
+  pub page: PathBuf,
+
+  /// List what's on the page without writing anything
+  #[arg(long)]
+  pub dry_run: bool,
+
+  /// Replace example files and a manifest that already exist
+  #[arg(long)]
+  pub force: bool,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+#[derive(Debug, Default, Clone, Args)]
+pub struct Selection {
+  /// Only run this day
+  #[arg(long, conflicts_with = "days")]
+  pub day: Option<u8>,
+
+  /// Only run these days, e.g. `1-10` or `1,3,5-7`
+  #[arg(long, value_parser = parse_days)]
+  pub days: Option<DaySet>,
+
+  /// Only run this part
+  #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+  pub part: Option<u8>,
+
+  /// Solve this file instead of the day's real input, or `-` to read it from stdin
+  #[arg(long, requires = "day")]
+  pub input: Option<PathBuf>,
+
+  /// Set a param for the real input, e.g. `--param width=11`
+  #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+  pub params: Vec<(String, String)>,
+
+  /// Give up on a part after this many seconds, e.g. `--timeout 10` for every part, `--timeout
+  /// 14=30` for day 14, or `--timeout 14.2=60` for its part 2
+  #[arg(long = "timeout", value_name = "[DAY[.PART]=]SECONDS", value_parser = parse_timeout)]
+  pub timeouts: Vec<Timeout>,
+
+  #[command(flatten)]
+  pub input_dir: InputDirArgs,
+}
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+const DEFAULT_INPUT_DIR: &str = "input";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(BTreeSet<u8>);
+
+impl Selection {
+  pub fn includes_day(&self, day: u8) -> bool {
+    match (self.day, &self.days) {
+      (Some(selected), _) => selected == day,
+      (None, Some(DaySet(days))) => days.contains(&day),
+      (None, None) => true,
+    }
+  }
+
+  /// The days `--day` or `--days` named, or none if every day is selected.
+  pub fn named_days(&self) -> Vec<u8> {
+    match (self.day, &self.days) {
+      (Some(day), _) => vec![day],
+      (None, Some(DaySet(days))) => days.iter().copied().collect(),
+      (None, None) => Vec::new(),
+    }
+  }
+
+  pub fn includes_part(&self, part: u8) -> bool {
+    self.part.is_none_or(|selected| selected == part)
+  }
+
+  pub fn params(&self) -> Params {
+    self.params.iter().cloned().collect()
+  }
+
+  /// The time limit for `part` of `day`, if `--timeout` gave one.
+  pub fn timeout(&self, day: u8, part: u8) -> Option<Duration> {
+    cancel::limit(&self.timeouts, day, part)
+  }
+}
+
+#[derive(Debug, Default, Clone, Args)]
+pub struct InputDirArgs {
+  /// Where the `dayN` input directories are. Defaults to `$AOC_INPUT_DIR`, or `input`
+  #[arg(long)]
+  input_dir: Option<PathBuf>,
+}
+
+impl InputDirArgs {
+  pub fn path(&self) -> PathBuf {
+    self
+      .input_dir
+      .clone()
+      .or_else(|| env::var_os(INPUT_DIR_VAR).map(PathBuf::from))
+      .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+  }
+}
+
+fn parse_day(value: &str) -> Result<u8, String> {
+  value
+    .trim()
+    .parse::<u8>()
+    .map_err(|_| format!("`{value}` is not a day number"))
+}
+
+fn parse_trace_day(value: &str) -> Result<u8, String> {
+  parse_day(value.strip_prefix("day").unwrap_or(value))
+}
+
+fn parse_param(value: &str) -> Result<(String, String), String> {
+  match value.split_once('=') {
+    Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+    _ => Err(format!("`{value}` isn't of the form NAME=VALUE")),
+  }
+}
+
+fn parse_timeout(value: &str) -> Result<Timeout, String> {
+  let (target, seconds) = match value.split_once('=') {
+    Some((target, seconds)) => (Some(target), seconds),
+    None => (None, value),
+  };
+
+  let (day, part) = match target.map(|target| target.split_once('.').ok_or(target)) {
+    None => (None, None),
+    Some(Ok((day, part))) => (Some(day), Some(part)),
+    Some(Err(day)) => (Some(day), None),
+  };
+  let day = day.map(parse_day).transpose()?;
+  let part = part
+    .map(|part| match part {
+      "1" => Ok(1),
+      "2" => Ok(2),
+      _ => Err(format!("`{part}` is not a part, expected 1 or 2")),
+    })
+    .transpose()?;
+
+  let limit = parse_seconds(seconds)?;
+
+  Ok(Timeout { day, part, limit })
+}
+
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+  value
+    .parse::<f64>()
+    .ok()
+    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+    .ok_or_else(|| format!("`{value}` is not a number of seconds"))
+}
+
+fn parse_days(value: &str) -> Result<DaySet, String> {
+  let mut days = BTreeSet::new();
+
+  for item in value.split(',') {
+    if let Some((start, end)) = item.split_once('-') {
+      let (start, end) = (parse_day(start)?, parse_day(end)?);
+      if start > end {
+        return Err(format!("`{item}` is not an ascending range"));
+      }
+      days.extend(start..=end);
+    } else {
+      days.insert(parse_day(item)?);
+    }
+  }
+
+  Ok(DaySet(days))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_log_args() {
+    let cli = Cli::parse_from([
+      "advent2024",
+      "check",
+      "-vv",
+      "--trace",
+      "day15",
+      "--trace",
+      "3",
+    ]);
+    assert_eq!(cli.log.verbose, 2);
+    assert_eq!(cli.log.trace, vec![15, 3]);
+    assert!(Cli::try_parse_from(["advent2024", "--trace", "dayx"]).is_err());
+  }
+
+  #[test]
+  fn test_jobs() {
+    let run = |args: &[&str]| Cli::try_parse_from(["advent2024", "run"].iter().chain(args));
+    let jobs = |args: &[&str]| match run(args).unwrap().command {
+      Some(Command::Run(args)) => args.jobs(),
+      _ => panic!("Expected the run command"),
+    };
+
+    assert_eq!(jobs(&[]), None);
+    assert_eq!(jobs(&["--jobs", "4"]), Some(4));
+    assert_eq!(jobs(&["--sequential"]), Some(1));
+    assert!(run(&["--jobs", "0"]).is_err());
+    assert!(run(&["--jobs", "2", "--sequential"]).is_err());
+  }
+
+  #[test]
+  fn test_parse_timeout() {
+    let timeout = |day, part, millis| Timeout {
+      day,
+      part,
+      limit: Duration::from_millis(millis),
+    };
+    assert_eq!(parse_timeout("10"), Ok(timeout(None, None, 10_000)));
+    assert_eq!(parse_timeout("14=0.5"), Ok(timeout(Some(14), None, 500)));
+    assert_eq!(
+      parse_timeout("14.2=60"),
+      Ok(timeout(Some(14), Some(2), 60_000))
+    );
+    assert!(parse_timeout("14.3=60").is_err());
+    assert!(parse_timeout("14=-1").is_err());
+    assert!(parse_timeout("x=1").is_err());
+  }
+
+  #[test]
+  fn test_parse_days_range() {
+    assert_eq!(parse_days("1-3"), Ok(DaySet(BTreeSet::from([1, 2, 3]))));
+  }
+
+  #[test]
+  fn test_parse_days_list() {
+    assert_eq!(
+      parse_days("1,3,5-7"),
+      Ok(DaySet(BTreeSet::from([1, 3, 5, 6, 7])))
+    );
+  }
+
+  #[test]
+  fn test_parse_days_invalid() {
+    assert!(parse_days("3-1").is_err());
+    assert!(parse_days("x").is_err());
+  }
+
+  #[test]
+  fn test_selection() {
+    let cli = Cli::parse_from(["advent2024", "run", "--days", "2-4", "--part", "2"]);
+    let Some(Command::Run(RunArgs { selection, .. })) = cli.command else {
+      panic!("Expected the run command");
+    };
+
+    assert!(!selection.includes_day(1));
+    assert!(selection.includes_day(3));
+    assert_eq!(selection.named_days(), vec![2, 3, 4]);
+    assert!(!selection.includes_part(1));
+    assert!(selection.includes_part(2));
+  }
+
+  #[test]
+  fn test_named_days() {
+    let selection = |args: &[&str]| {
+      let cli = Cli::parse_from(["advent2024", "run"].iter().chain(args));
+      let Some(Command::Run(args)) = cli.command else {
+        panic!("Expected the run command");
+      };
+      args.selection.named_days()
+    };
+
+    assert_eq!(selection(&["--day", "30"]), vec![30]);
+    assert_eq!(selection(&["--days", "30-31"]), vec![30, 31]);
+    assert_eq!(selection(&[]), Vec::<u8>::new());
+  }
+
+  #[test]
+  fn test_format() {
+    let cli = Cli::parse_from(["advent2024", "check", "--format", "junit"]);
+    let Some(Command::Check(args)) = cli.command else {
+      panic!("Expected the check command");
+    };
+
+    assert_eq!(args.run.format, Format::Junit);
+    assert!(!args.examples_only);
+  }
+
+  #[test]
+  fn test_bench_args() {
+    let cli = Cli::parse_from(["advent2024", "bench", "--day", "22", "--budget", "2.5"]);
+    let Some(Command::Bench(args)) = cli.command else {
+      panic!("Expected the bench command");
+    };
+
+    assert_eq!(args.selection.day, Some(22));
+    assert_eq!(args.budget, Some(Duration::from_millis(2500)));
+    assert_eq!(args.warmup, 1);
+    assert!(Cli::try_parse_from(["advent2024", "bench", "--runs", "3", "--budget", "1"]).is_err());
+    assert!(Cli::try_parse_from(["advent2024", "bench", "--runs", "0"]).is_err());
+    for budget in ["-1", "NaN", "inf"] {
+      assert!(Cli::try_parse_from(["advent2024", "bench", "--budget", budget]).is_err());
+    }
+  }
+
+  #[test]
+  fn test_input_dir() {
+    let cli = Cli::parse_from(["advent2024", "run", "--input-dir", "/tmp/aoc"]);
+    let Some(Command::Run(args)) = cli.command else {
+      panic!("Expected the run command");
+    };
+
+    assert_eq!(args.selection.input_dir.path(), PathBuf::from("/tmp/aoc"));
+  }
+
+  #[test]
+  fn test_params() {
+    let cli = Cli::parse_from([
+      "advent2024",
+      "run",
+      "--day",
+      "14",
+      "--param",
+      "width=11",
+      "--param",
+      "height=7",
+    ]);
+    let Some(Command::Run(args)) = cli.command else {
+      panic!("Expected the run command");
+    };
+
+    assert_eq!(
+      args.selection.params(),
+      Params::from([("width", "11"), ("height", "7")])
+    );
+    assert!(parse_param("width").is_err());
+  }
+
+  #[test]
+  fn test_new_args() {
+    let cli = Cli::parse_from(["advent2024", "new", "--day", "26"]);
+    let Some(Command::New(args)) = cli.command else {
+      panic!("Expected the new command");
+    };
+
+    assert_eq!((args.year, args.day), (registry::YEAR, 26));
+    assert!(Cli::try_parse_from(["advent2024", "new", "--day", "0"]).is_err());
+    assert!(Cli::try_parse_from(["advent2024", "new"]).is_err());
+  }
+
+  #[test]
+  fn test_answer_args() {
+    let cli = Cli::parse_from([
+      "advent2024",
+      "answer",
+      "--day",
+      "3",
+      "--part",
+      "2",
+      "--reject",
+      "12",
+      "--hint",
+      "too-low",
+    ]);
+    let Some(Command::Answer(args)) = cli.command else {
+      panic!("Expected the answer command");
+    };
+
+    assert_eq!(args.reject.as_deref(), Some("12"));
+    assert_eq!(args.hint, Some(Hint::TooLow));
+
+    let answer = |args: &[&str]| {
+      Cli::try_parse_from(
+        ["advent2024", "answer", "--day", "3", "--part", "2"]
+          .iter()
+          .chain(args),
+      )
+    };
+    assert!(answer(&[]).is_err());
+    assert!(answer(&["--accept", "1", "--reject", "2"]).is_err());
+    assert!(answer(&["--accept", "1", "--hint", "too-high"]).is_err());
+  }
+
+  #[test]
+  fn test_input_requires_day() {
+    assert!(Cli::try_parse_from(["advent2024", "run", "--input", "x.txt"]).is_err());
+  }
+}
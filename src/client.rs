@@ -0,0 +1,106 @@
+use std::env;
+use std::fmt;
+use std::time::Duration;
+
+/// Where puzzles are served from, unless `--url` or `$AOC_URL` says otherwise.
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+pub const URL_VAR: &str = "AOC_URL";
+/// The `session` cookie of a logged in browser.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "github.com/mrtom/advent2024";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+  /// No session token was given, so the site can't tell who we are.
+  NoSession,
+  /// The site responded, but not with success.
+  Status { code: u16, body: String },
+  /// The site couldn't be reached.
+  Transport(String),
+}
+
+impl fmt::Display for ClientError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ClientError::NoSession => write!(f, "no session token, set ${SESSION_VAR}"),
+      ClientError::Status { code, .. } => write!(f, "the site responded with status {code}"),
+      ClientError::Transport(message) => write!(f, "couldn't reach the site: {message}"),
+    }
+  }
+}
+
+impl From<ureq::Error> for ClientError {
+  fn from(err: ureq::Error) -> Self {
+    match err {
+      ureq::Error::Status(code, response) => ClientError::Status {
+        code,
+        body: response.into_string().unwrap_or_default(),
+      },
+      ureq::Error::Transport(transport) => ClientError::Transport(transport.to_string()),
+    }
+  }
+}
+
+/// Talks to the puzzle site as a logged in user.
+pub struct Client {
+  base_url: String,
+  session: String,
+  agent: ureq::Agent,
+}
+
+impl Client {
+  pub fn new(base_url: &str, session: &str) -> Self {
+    Client {
+      base_url: base_url.trim_end_matches('/').to_string(),
+      session: session.to_string(),
+      agent: ureq::AgentBuilder::new()
+        .user_agent(USER_AGENT)
+        .timeout(TIMEOUT)
+        .build(),
+    }
+  }
+
+  /// A client for `base_url`, or `$AOC_URL`, or the real site, logged in with `$AOC_SESSION`.
+  pub fn from_env(base_url: Option<&str>) -> Result<Self, ClientError> {
+    let session = env::var(SESSION_VAR).map_err(|_| ClientError::NoSession)?;
+    let base_url = base_url
+      .map(ToString::to_string)
+      .or_else(|| env::var(URL_VAR).ok())
+      .unwrap_or_else(|| DEFAULT_URL.to_string());
+    Ok(Client::new(&base_url, session.trim()))
+  }
+
+  fn cookie(&self) -> String {
+    format!("session={}", self.session)
+  }
+
+  /// Downloads the puzzle input for `day`, which is different for every user.
+  pub fn fetch_input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+    let url = format!("{}/{year}/day/{day}/input", self.base_url);
+    let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+    response
+      .into_string()
+      .map_err(|err| ClientError::Transport(err.to_string()))
+  }
+
+  /// Posts `answer` for `part` of the puzzle, returning the page the site responded with.
+  pub fn submit_answer(
+    &self,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+  ) -> Result<String, ClientError> {
+    let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+    let response = self
+      .agent
+      .post(&url)
+      .set("Cookie", &self.cookie())
+      .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+    response
+      .into_string()
+      .map_err(|err| ClientError::Transport(err.to_string()))
+  }
+}
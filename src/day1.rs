@@ -1,61 +1,64 @@
-use regex::Regex;
+use regex::{Captures, Regex};
 
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::Convert;
 use crate::AOCDay;
 
 pub struct Day1 {}
 
-fn parse_input(input: &[String]) -> (Vec<i32>, Vec<i32>) {
+pub const SOLUTION: Solution = Solution::new(2024, 1, &Day1 {});
+
+fn parse_number(caps: &Captures, group: usize, line_idx: usize) -> Result<i32, SolveError> {
+  let capture = caps.get(group).expect("Failed to get capture group");
+  capture.as_str().parse::<i32>().map_err(|msg| {
+    SolveError::parse(line_idx, format!("failed to parse number: {msg}")).at_column(capture.start())
+  })
+}
+
+fn parse_input(input: &[String]) -> Result<(Vec<i32>, Vec<i32>), SolveError> {
   let mut first: Vec<i32> = Vec::new();
   let mut second: Vec<i32> = Vec::new();
 
-  for line in input {
-    let re = match Regex::new(r"(\d+)\s+(\d+)") {
-      Ok(re) => re,
-      Err(msg) => panic!("Failed to create regex: {msg}"),
-    };
+  let re = match Regex::new(r"(\d+)\s+(\d+)") {
+    Ok(re) => re,
+    Err(msg) => panic!("Failed to create regex: {msg}"),
+  };
 
+  for (line_idx, line) in input.iter().enumerate() {
     let Some(caps) = re.captures(line) else {
-      panic!("Failed to match regex");
+      return Err(SolveError::parse(line_idx, "expected two numbers"));
     };
 
-    let left = match caps.get(1) {
-      Some(m) => match m.as_str().parse::<i32>() {
-        Ok(num) => num,
-        Err(msg) => panic!("Failed to parse left number, {msg}"),
-      },
-      None => panic!("Failed to get left capture group"),
-    };
-
-    let right = match caps.get(2) {
-      Some(m) => match m.as_str().parse::<i32>() {
-        Ok(num) => num,
-        Err(msg) => panic!("Failed to parse right number: {msg}"),
-      },
-      None => panic!("Failed to get right capture group"),
-    };
-
-    first.push(left);
-    second.push(right);
+    first.push(parse_number(&caps, 1, line_idx)?);
+    second.push(parse_number(&caps, 2, line_idx)?);
   }
 
-  (first, second)
+  Ok((first, second))
 }
 
 impl AOCDay for Day1 {
+  type Input = (Vec<i32>, Vec<i32>);
+
   fn name(&self) -> String {
     "day1".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    "11".to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some("11".to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some("31".to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    "31".to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (mut first, mut second) = parse_input(input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (mut first, mut second) = input.clone();
 
     first.sort_unstable();
     second.sort_unstable();
@@ -66,24 +69,21 @@ impl AOCDay for Day1 {
       .map(|(i, &x)| (second[i] - x).abs())
       .sum();
 
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (first, second): (Vec<i32>, Vec<i32>) = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (first, second) = input;
 
     let result: i32 = first
       .iter()
       .map(|&x| {
-        let count = match i32::try_from(second.iter().filter(|&y| *y == x).count()) {
-          Ok(count) => count,
-          Err(msg) => panic!("Failed to convert count to i32: {msg}"),
-        };
+        let count: i32 = second.iter().filter(|&y| *y == x).count().convert_x();
         x * count
       })
       .sum();
 
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
@@ -92,12 +92,41 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  #[test]
+  fn test_parse_error() {
+    let day = Day1 {};
+    let input = vec!["3   4".to_string(), "4 x".to_string()];
+    assert_eq!(
+      day.parse(&input),
+      Err(SolveError::parse(1, "expected two numbers"))
+    );
+  }
+
+  #[test]
+  fn test_solve_str() {
+    let day = Day1 {};
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    assert_eq!(
+      crate::Solver::solve_str(&day, 1, input, &Params::default()),
+      Ok("11".to_string())
+    );
+    assert_eq!(
+      crate::Solver::solve_str(&day, 2, input, &Params::default()),
+      Ok("31".to_string())
+    );
+  }
+
   #[test]
   fn test_part_1() {
     let day = Day1 {};
     assert_eq!(
       "1882714",
-      day.solve_part1(&read_file("input/day1/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day1/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -106,7 +135,12 @@ mod tests {
     let day = Day1 {};
     assert_eq!(
       "19437052",
-      day.solve_part2(&read_file("input/day1/part2.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day1/part2.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
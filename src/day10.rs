@@ -1,141 +1,86 @@
 use std::collections::HashSet;
 
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Grid, Point};
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "36";
 const PART_2_EXAMPLE: &str = "81";
 
-type Map = Vec<Vec<u32>>;
-type Position = (usize, usize);
-
-fn parse_input(input: &[String]) -> Vec<Vec<u32>> {
-  input
-    .iter()
-    .map(|str| {
-      str
-        .chars()
-        .map(|char| char.to_digit(10).unwrap_or(u32::MAX))
-        .collect::<Vec<u32>>()
-    })
-    .collect::<Vec<Vec<u32>>>()
-}
-
-fn find_valid_neighbours(
-  map: &Map,
-  current_position: Position,
-  previous_position: Position,
-) -> Vec<Position> {
-  let (row, col) = current_position;
-  let (prev_row, prev_col) = previous_position;
-  let mut neighbors = Vec::new();
-
-  if row > 0 && row - 1 != prev_row {
-    neighbors.push((row - 1, col)); // Up
-  }
-  if row < map.len() - 1 && row + 1 != prev_row {
-    neighbors.push((row + 1, col)); // Down
-  }
-  if col > 0 && col - 1 != prev_col {
-    neighbors.push((row, col - 1)); // Left
-  }
-  if col < map[0].len() - 1 && col + 1 != prev_col {
-    neighbors.push((row, col + 1)); // Right
-  }
-
-  neighbors
-}
-
 fn walk(
-  map: &Map,
-  peaks: &mut HashSet<Position>,
+  map: &Grid<u32>,
+  peaks: &mut HashSet<Point>,
   trails: &mut HashSet<String>,
-  current_position: Position,
-  previous_position: Position,
+  current_position: Point,
   route: &str,
 ) {
-  let (row, col) = current_position;
+  let route = format!("{route}:{current_position},");
 
-  if map[row][col] == 9 {
+  if map[current_position] == 9 {
     peaks.insert(current_position);
-    trails.insert(format!("{route}:{row},{col},"));
+    trails.insert(route);
     return;
   }
 
-  let neighbours = find_valid_neighbours(map, current_position, previous_position);
-  for &neighbour in &neighbours {
-    if map[neighbour.0][neighbour.1] == map[row][col] + 1 {
-      let new_route = format!("{route}:{row},{col},");
-      walk(map, peaks, trails, neighbour, current_position, &new_route);
+  // Trails only go uphill, so they can never lead back to where they came from
+  for neighbour in map.neighbours(current_position) {
+    if map[neighbour] == map[current_position] + 1 {
+      walk(map, peaks, trails, neighbour, &route);
     }
   }
 }
 
-fn score_trail(map: &Map, trailhead: Position) -> (usize, usize) {
-  let mut peaks: HashSet<Position> = HashSet::new();
+fn score_trail(map: &Grid<u32>, trailhead: Point) -> (usize, usize) {
+  let mut peaks: HashSet<Point> = HashSet::new();
   let mut trails: HashSet<String> = HashSet::new();
-  walk(
-    map,
-    &mut peaks,
-    &mut trails,
-    trailhead,
-    (usize::MAX, usize::MAX),
-    "",
-  );
+  walk(map, &mut peaks, &mut trails, trailhead, "");
 
   (peaks.len(), trails.len())
 }
 
 pub struct Day10 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 10, &Day10 {});
+
 impl AOCDay for Day10 {
+  type Input = Grid<u32>;
+
   fn name(&self) -> String {
     "day10".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(input);
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    Grid::parse(input, |char| Some(char.to_digit(10).unwrap_or(u32::MAX)))
+  }
 
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
     let result = input
       .iter()
-      .enumerate()
-      .flat_map(|(row, _)| {
-        input[row]
-          .iter()
-          .enumerate()
-          .filter(|(_, value)| **value == 0)
-          .map(|(col, _)| score_trail(&input, (row, col)).0)
-          .collect::<Vec<usize>>()
-      })
+      .filter(|(_, &height)| height == 0)
+      .map(|(trailhead, _)| score_trail(input, trailhead).0)
       .sum::<usize>();
 
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
     let result = input
       .iter()
-      .enumerate()
-      .flat_map(|(row, _)| {
-        input[row]
-          .iter()
-          .enumerate()
-          .filter(|(_, value)| **value == 0)
-          .map(|(col, _)| score_trail(&input, (row, col)).1)
-          .collect::<Vec<usize>>()
-      })
+      .filter(|(_, &height)| height == 0)
+      .map(|(trailhead, _)| score_trail(input, trailhead).1)
       .sum::<usize>();
 
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
@@ -149,14 +94,27 @@ mod tests {
     let day = Day10 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day10/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day10/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day10 {};
-    assert_eq!("557", day.solve_part1(&read_file("input/day10/part1.txt")));
+    assert_eq!(
+      "557",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day10/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
@@ -172,7 +130,12 @@ mod tests {
       "..9....".to_string(),
     ];
 
-    assert_eq!("3", day.solve_part2(&input));
+    assert_eq!(
+      "3",
+      day
+        .solve_part2(&day.parse(&input).unwrap(), &Params::default())
+        .unwrap()
+    );
   }
 
   #[test]
@@ -188,7 +151,12 @@ mod tests {
       "987....".to_string(),
     ];
 
-    assert_eq!("13", day.solve_part2(&input));
+    assert_eq!(
+      "13",
+      day
+        .solve_part2(&day.parse(&input).unwrap(), &Params::default())
+        .unwrap()
+    );
   }
 
   #[test]
@@ -196,13 +164,26 @@ mod tests {
     let day = Day10 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day10/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day10/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day10 {};
-    assert_eq!("1062", day.solve_part2(&read_file("input/day10/part1.txt")));
+    assert_eq!(
+      "1062",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day10/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
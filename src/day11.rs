@@ -1,11 +1,22 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
 use crate::AOCDay;
 use std::collections::HashMap;
 
 const PART_1_EXAMPLE: &str = "55312";
 const PART_2_EXAMPLE: &str = "65601038650482";
 
-fn parse_input(input: &str) -> Vec<usize> {
-  input.split(' ').map(|s| s.parse().unwrap()).collect()
+fn parse_input(input: &[String]) -> Result<Vec<usize>, SolveError> {
+  let Some(line) = input.first() else {
+    return Err(SolveError::parse(0, "expected a line of stones"));
+  };
+
+  line
+    .split(' ')
+    .map(str::parse::<usize>)
+    .collect::<Result<Vec<usize>, _>>()
+    .map_err(|msg| SolveError::parse(0, format!("failed to parse stone: {msg}")))
 }
 
 fn apply_rules(rock: usize) -> (usize, Option<usize>) {
@@ -41,21 +52,28 @@ fn blink(map: &HashMap<usize, usize>) -> HashMap<usize, usize> {
 
 pub struct Day11 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 11, &Day11 {});
+
 impl AOCDay for Day11 {
+  type Input = Vec<usize>;
+
   fn name(&self) -> String {
     "day11".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(&input[0]);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
     let mut map: HashMap<usize, usize> = input.iter().map(|value| (*value, 1)).collect();
 
     for _ in 0..25 {
@@ -63,11 +81,10 @@ impl AOCDay for Day11 {
     }
 
     let result = map.values().sum::<usize>();
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let input = parse_input(&input[0]);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
     let mut map: HashMap<usize, usize> = input.iter().map(|value| (*value, 1)).collect();
 
     for _ in 0..75 {
@@ -75,7 +92,7 @@ impl AOCDay for Day11 {
     }
 
     let result = map.values().sum::<usize>();
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
@@ -89,7 +106,12 @@ mod tests {
     let day = Day11 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day11/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day11/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -98,7 +120,12 @@ mod tests {
     let day = Day11 {};
     assert_eq!(
       "218956",
-      day.solve_part1(&read_file("input/day11/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day11/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -107,7 +134,12 @@ mod tests {
     let day = Day11 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day11/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day11/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -116,7 +148,12 @@ mod tests {
     let day = Day11 {};
     assert_eq!(
       "259593838049805",
-      day.solve_part2(&read_file("input/day11/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day11/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -1,237 +1,176 @@
+use tracing::debug;
+
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Grid, Point};
 use crate::AOCDay;
 
-// 140
-// 772
-// 1930
 const PART_1_EXAMPLE: &str = "1930";
 
-// 80
-// 436
-// 236
-// 368
-// 1206
 const PART_2_EXAMPLE: &str = "1206";
 
-type Map = Vec<Vec<Cell>>;
-
-struct Cell {
+#[derive(Clone)]
+pub struct Cell {
   crop: char,
   island_id: Option<usize>,
-  row: usize,
-  col: usize,
-}
-
-fn parse_input(input: &[String]) -> Vec<Vec<Cell>> {
-  input
-    .iter()
-    .enumerate()
-    .map(|(row_idx, line)| {
-      line
-        .chars()
-        .enumerate()
-        .map(|(col_idx, char)| Cell {
-          crop: char,
-          island_id: None,
-          row: row_idx,
-          col: col_idx,
-        })
-        .collect()
-    })
-    .collect()
-}
-
-fn find_valid_neighbours(map: &Map, row: usize, col: usize) -> Vec<(usize, usize)> {
-  let mut neighbors = Vec::new();
-
-  if row > 0 {
-    neighbors.push((row - 1, col)); // Up
-  }
-  if row < map.len() - 1 {
-    neighbors.push((row + 1, col)); // Down
-  }
-  if col > 0 {
-    neighbors.push((row, col - 1)); // Left
-  }
-  if col < map[0].len() - 1 {
-    neighbors.push((row, col + 1)); // Right
-  }
-
-  neighbors
-}
-
-fn find_detached_neighbours(map: &Map, row: usize, col: usize) -> Vec<(usize, usize)> {
-  find_valid_neighbours(map, row, col)
-    .iter()
-    .filter(|(r, c)| map[*r][*c].island_id.is_none())
-    .map(|(r, c)| (*r, *c))
-    .collect()
 }
 
-#[allow(dead_code)]
-fn print_map(map: &Map) {
-  for row in map {
-    for cell in row {
-      let id: String = match cell.island_id {
-        Some(id) => id.to_string(),
-        None => "#".to_string(),
-      };
-      print!("{}{}", cell.crop, id);
+impl std::fmt::Display for Cell {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    match self.island_id {
+      Some(id) => write!(f, "{}{id}", self.crop),
+      None => write!(f, "{}#", self.crop),
     }
-    println!();
   }
-  println!("-----------------\n");
 }
 
-fn grow_island(map: &mut Map, row: usize, col: usize, id: usize) {
-  let current_crop = map[row][col].crop;
-  map[row][col].island_id = Some(id);
-  for neighbour in find_detached_neighbours(map, row, col) {
-    if map[neighbour.0][neighbour.1].crop == current_crop {
-      grow_island(map, neighbour.0, neighbour.1, id);
+fn grow_island(map: &mut Grid<Cell>, point: Point, id: usize) {
+  let current_crop = map[point].crop;
+  map[point].island_id = Some(id);
+  for neighbour in map.neighbours(point) {
+    if map[neighbour].island_id.is_none() && map[neighbour].crop == current_crop {
+      grow_island(map, neighbour, id);
     }
   }
 }
 
-fn find_islands(map: &mut Map) -> usize {
+fn find_islands(map: &mut Grid<Cell>) -> usize {
   let mut max_id = 0;
-  let row_max = map.len();
-  let col_max = map[0].len();
-
-  for row_idx in 0..row_max {
-    for col_idx in 0..col_max {
-      if Option::is_none(&map[row_idx][col_idx].island_id) {
-        max_id += 1;
-        grow_island(map, row_idx, col_idx, max_id);
-      }
+
+  for point in map.points() {
+    if map[point].island_id.is_none() {
+      max_id += 1;
+      grow_island(map, point, max_id);
     }
   }
 
   max_id
 }
 
-fn calculate_perimeter_length(map: &Map, row: usize, col: usize) -> usize {
-  let mut perimeter = 4;
-  let current_crop = map[row][col].crop;
-  for neighbour in find_valid_neighbours(map, row, col) {
-    if map[neighbour.0][neighbour.1].crop == current_crop {
-      perimeter -= 1;
-    }
-  }
-  perimeter
+fn calculate_perimeter_length(map: &Grid<Cell>, point: Point) -> usize {
+  let current_crop = map[point].crop;
+  let same_crop = map
+    .neighbours(point)
+    .filter(|&neighbour| map[neighbour].crop == current_crop)
+    .count();
+  4 - same_crop
 }
 
-fn calculate_island_perimeter(map: &Map, island_id: usize) -> usize {
+fn calculate_island_perimeter(map: &Grid<Cell>, island_id: usize) -> usize {
   map
     .iter()
-    .flat_map(|row| row.iter())
-    .filter(|cell| cell.island_id == Some(island_id))
-    .map(|cell| calculate_perimeter_length(map, cell.row, cell.col))
+    .filter(|(_, cell)| cell.island_id == Some(island_id))
+    .map(|(point, _)| calculate_perimeter_length(map, point))
     .sum()
 }
 
-fn calculate_number_of_sides(map: &Map, row: usize, col: usize) -> usize {
-  let mut num_sides = calculate_perimeter_length(map, row, col);
-  let current_crop = map[row][col].crop;
+fn calculate_number_of_sides(map: &Grid<Cell>, point: Point) -> usize {
+  let mut num_sides = calculate_perimeter_length(map, point);
+  let current_crop = map[point].crop;
+
+  // Whether the cell at this offset is the same crop. Cells outside the map never are.
+  let same = |dx: i32, dy: i32| {
+    map
+      .get(point + Point::new(dx, dy))
+      .is_some_and(|cell| cell.crop == current_crop)
+  };
 
   // We reduce the top edge by one if the cell to the left is the same crop AND
   // the cell above is not the same crop AND
-  // the cell above and to the left is not the same crop (or is out of bounds)
-  if (col > 0 && map[row][col - 1].crop == current_crop)
-    && (row == 0 || map[row - 1][col].crop != current_crop)
-    && (row > 0 && col > 0 && map[row - 1][col - 1].crop != current_crop || row == 0 && col > 0)
-  {
+  // the cell above and to the left is not the same crop
+  if same(-1, 0) && !same(0, -1) && !same(-1, -1) {
     num_sides -= 1;
   }
 
   // We reduce the bottom edge by one if the cell to the left is the same crop AND
   // the cell below is not the same crop AND
-  // the cell below and to the left is not the same crop (or is out of bounds)
-  if (col > 0 && map[row][col - 1].crop == current_crop)
-    && (row == map.len() - 1 || map[row + 1][col].crop != current_crop)
-    && (row < map.len() - 1 && col > 0 && map[row + 1][col - 1].crop != current_crop
-      || row == map.len() - 1 && col > 0)
-  {
+  // the cell below and to the left is not the same crop
+  if same(-1, 0) && !same(0, 1) && !same(-1, 1) {
     num_sides -= 1;
   }
 
   // We reduce the left edge by one if the cell above is the same crop AND
   // the cell to the left is not the same crop AND
-  // the cell above and to the left is not the same crop (or is out of bounds)
-  if (row > 0 && map[row - 1][col].crop == current_crop)
-    && (col == 0 || map[row][col - 1].crop != current_crop)
-    && (row > 0 && col > 0 && map[row - 1][col - 1].crop != current_crop || row > 0 && col == 0)
-  {
+  // the cell above and to the left is not the same crop
+  if same(0, -1) && !same(-1, 0) && !same(-1, -1) {
     num_sides -= 1;
   }
 
   // We reduce the right edge by one if the cell above is the same crop AND
   // the cell to the right is not the same crop AND
-  // the cell above and to the right is not the same crop (or is out of bounds)
-  if (row > 0 && map[row - 1][col].crop == current_crop)
-    && (col == map[0].len() - 1 || map[row][col + 1].crop != current_crop)
-    && (row > 0 && col < map[0].len() - 1 && map[row - 1][col + 1].crop != current_crop
-      || row > 0 && col == map[0].len() - 1)
-  {
+  // the cell above and to the right is not the same crop
+  if same(0, -1) && !same(1, 0) && !same(1, -1) {
     num_sides -= 1;
   }
 
   num_sides
 }
 
-fn calculate_island_num_of_sides(map: &Map, island_id: usize) -> usize {
-  let num_sides = map
+fn calculate_island_num_of_sides(map: &Grid<Cell>, island_id: usize) -> usize {
+  map
     .iter()
-    .flat_map(|row| row.iter())
-    .filter(|cell| cell.island_id == Some(island_id))
-    .map(|cell| calculate_number_of_sides(map, cell.row, cell.col))
-    .sum();
-
-  num_sides
+    .filter(|(_, cell)| cell.island_id == Some(island_id))
+    .map(|(point, _)| calculate_number_of_sides(map, point))
+    .sum()
 }
 
-fn calculate_island_area(map: &Map, island_id: usize) -> usize {
+fn calculate_island_area(map: &Grid<Cell>, island_id: usize) -> usize {
   map
     .iter()
-    .flat_map(|row| row.iter())
-    .filter(|cell| cell.island_id == Some(island_id))
+    .filter(|(_, cell)| cell.island_id == Some(island_id))
     .count()
 }
 
 pub struct Day12 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 12, &Day12 {});
+
 impl AOCDay for Day12 {
+  type Input = Grid<Cell>;
+
   fn name(&self) -> String {
     "day12".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
+  }
+
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    Grid::parse(input, |crop| {
+      Some(Cell {
+        crop,
+        island_id: None,
+      })
+    })
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let mut map = parse_input(input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let mut map = input.clone();
     let max_id = find_islands(&mut map);
+    debug!(islands = max_id);
 
     let cost = (1..=max_id)
       .map(|id| calculate_island_perimeter(&map, id) * calculate_island_area(&map, id))
       .sum::<usize>();
 
-    cost.to_string()
+    Ok(cost.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let mut map = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let mut map = input.clone();
     let max_id = find_islands(&mut map);
 
     let cost = (1..=max_id)
       .map(|id| calculate_island_num_of_sides(&map, id) * calculate_island_area(&map, id))
       .sum::<usize>();
 
-    cost.to_string()
+    Ok(cost.to_string())
   }
 }
 
@@ -245,7 +184,12 @@ mod tests {
     let day = Day12 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day12/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day12/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -254,7 +198,12 @@ mod tests {
     let day = Day12 {};
     assert_eq!(
       "1465112",
-      day.solve_part1(&read_file("input/day12/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day12/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -263,7 +212,12 @@ mod tests {
     let day = Day12 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day12/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day12/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -272,7 +226,12 @@ mod tests {
     let day = Day12 {};
     assert_eq!(
       "893790",
-      day.solve_part2(&read_file("input/day12/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day12/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
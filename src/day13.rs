@@ -1,90 +1,65 @@
-use itertools::Itertools;
-use regex::Regex;
+use std::cmp::Ordering;
 
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::math::{self, MathError};
+use crate::utils::parse;
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "480";
 const PART_2_EXAMPLE: &str = "875318608908";
 
+/// Part 2 moves every prize this much further away on both axes
+const PART_2_PRIZE_OFFSET: i64 = 10_000_000_000_000;
+
+#[derive(Clone, Copy)]
 struct Button {
-  x_const: isize,
-  y_const: isize,
+  x_const: i64,
+  y_const: i64,
 }
 
+#[derive(Clone, Copy)]
 struct Prize {
-  x: isize,
-  y: isize,
+  x: i64,
+  y: i64,
 }
 
-struct Machine {
+#[derive(Clone, Copy)]
+pub struct Machine {
   button_a: Button,
   button_b: Button,
   prize: Prize,
 }
 
-fn parse_input(input: &[String], is_part_two: bool) -> Vec<Machine> {
-  let combined = input.iter().join("_");
+fn parse_input(input: &[String]) -> Result<Vec<Machine>, SolveError> {
+  // Each machine is three lines, followed by a blank line
+  parse::sections(input)
+    .map(|(first_line_idx, lines)| build_machine(lines, first_line_idx))
+    .collect()
+}
+
+/// The X and Y on the line at `offset` in a machine's lines.
+fn parse_coordinates(
+  lines: &[String],
+  first_line_idx: usize,
+  offset: usize,
+) -> Result<(i64, i64), SolveError> {
+  let line_idx = first_line_idx + offset;
+  let line = lines.get(offset).map_or("", String::as_str);
 
-  combined
-    .split("__")
-    .map(|machine_input| build_machine(machine_input, is_part_two))
-    .collect::<Vec<Machine>>()
+  match parse::ints(line_idx, line)?[..] {
+    [x, y] => Ok((x, y)),
+    _ => Err(SolveError::parse(line_idx, "expected an X and a Y")),
+  }
 }
 
-fn build_machine(input: &str, is_part_two: bool) -> Machine {
-  let button_regex = Regex::new(r"Button (A|B): X\+(\d+), Y\+(\d+)").unwrap();
-  let prize_regex = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
-  let extra: isize = if is_part_two { 10_000_000_000_000 } else { 0 };
-
-  let machine_input = input.split('_').collect::<Vec<&str>>();
-  let (a_input, b_input, prize_input) = (machine_input[0], machine_input[1], machine_input[2]);
-
-  let capture_a = button_regex.captures(a_input);
-  let capture_b = button_regex.captures(b_input);
-  let capture_prize = prize_regex.captures(prize_input);
-
-  let cap_a = capture_a.expect("Could not parse capture_a");
-  let cap_b = capture_b.expect("Could not parse capture_b");
-  let cap_prize = capture_prize.expect("Could not parse capture_prize");
-
-  let a_x = cap_a
-    .get(2)
-    .unwrap()
-    .as_str()
-    .parse::<isize>()
-    .expect("Could not parse a_x");
-  let a_y = cap_a
-    .get(3)
-    .unwrap()
-    .as_str()
-    .parse::<isize>()
-    .expect("Could not parse a_y");
-  let b_x = cap_b
-    .get(2)
-    .unwrap()
-    .as_str()
-    .parse::<isize>()
-    .expect("Could not parse b_x");
-  let b_y = cap_b
-    .get(3)
-    .unwrap()
-    .as_str()
-    .parse::<isize>()
-    .expect("Could not parse b_y");
-  let prize_x = cap_prize
-    .get(1)
-    .unwrap()
-    .as_str()
-    .parse::<isize>()
-    .expect("Could not parse prize_x");
-  let prize_y = cap_prize
-    .get(2)
-    .unwrap()
-    .as_str()
-    .parse::<isize>()
-    .expect("Could not parse prize_y");
-
-  Machine {
+fn build_machine(lines: &[String], first_line_idx: usize) -> Result<Machine, SolveError> {
+  let (a_x, a_y) = parse_coordinates(lines, first_line_idx, 0)?;
+  let (b_x, b_y) = parse_coordinates(lines, first_line_idx, 1)?;
+  let (prize_x, prize_y) = parse_coordinates(lines, first_line_idx, 2)?;
+
+  Ok(Machine {
     button_a: Button {
       x_const: a_x,
       y_const: a_y,
@@ -94,61 +69,143 @@ fn build_machine(input: &str, is_part_two: bool) -> Machine {
       y_const: b_y,
     },
     prize: Prize {
-      x: prize_x + extra,
-      y: prize_y + extra,
+      x: prize_x,
+      y: prize_y,
     },
-  }
+  })
 }
 
-#[allow(clippy::similar_names)]
-fn solve_for(machine: &Machine) -> isize {
+/// The fewest tokens that win the prize, if it can be won.
+fn solve_for(machine: &Machine) -> Result<Option<i64>, MathError> {
   let button_a = &machine.button_a;
   let button_b = &machine.button_b;
   let prize = &machine.prize;
-  let determinent = button_a.x_const * button_b.y_const - button_a.y_const * button_b.x_const;
 
-  if determinent == 0 {
-    return 0;
+  match math::solve_2x2(
+    [
+      [button_a.x_const, button_b.x_const],
+      [button_a.y_const, button_b.y_const],
+    ],
+    [prize.x, prize.y],
+  ) {
+    Ok((button_a_pushes, button_b_pushes)) if button_a_pushes >= 0 && button_b_pushes >= 0 => {
+      math::checked_add(math::checked_mul(button_a_pushes, 3)?, button_b_pushes).map(Some)
+    }
+    // The buttons move the claw along the line to the prize, so there's more than one way there
+    Err(MathError::NotUnique) => Ok(cheapest_along_line(machine)),
+    Err(MathError::Overflow) => Err(MathError::Overflow),
+    _ => Ok(None),
   }
+}
 
-  let button_a_numerator = prize.x * button_b.y_const - prize.y * button_b.x_const;
-  let button_b_numerator = button_a.x_const * prize.y - button_a.y_const * prize.x;
-  if button_a_numerator % determinent != 0 || button_b_numerator % determinent != 0 {
-    return 0;
+/// The fewest tokens that win every prize that can be won.
+fn total_tokens(machines: impl Iterator<Item = Machine>) -> Answer {
+  let overflow = |err| SolveError::NoSolution(format!("the tokens needed {err}"));
+
+  let mut total = 0;
+  for machine in machines {
+    if let Some(tokens) = solve_for(&machine).map_err(overflow)? {
+      total = math::checked_add(total, tokens).map_err(overflow)?;
+    }
   }
 
-  let button_a_pushes = button_a_numerator / determinent;
-  let button_b_pushes = button_b_numerator / determinent;
+  Ok(total.to_string())
+}
+
+/// The fewest tokens when the buttons move the claw in the same direction. Every way of reaching
+/// the prize is `(a + k * step_a, b + k * step_b)` pushes, and the cost changes linearly with `k`,
+/// so the cheapest is at one end of the range of `k` where neither count is negative.
+fn cheapest_along_line(machine: &Machine) -> Option<i64> {
+  let button_a = &machine.button_a;
+  let button_b = &machine.button_b;
+  let prize = &machine.prize;
+
+  // Either axis will do, as long as the buttons move the claw along it
+  let (a_move, b_move, target) = if button_a.x_const != 0 || button_b.x_const != 0 {
+    (button_a.x_const, button_b.x_const, prize.x)
+  } else if prize.x == 0 {
+    (button_a.y_const, button_b.y_const, prize.y)
+  } else {
+    return None;
+  };
+
+  let pushes = match math::solve_diophantine(a_move, b_move, target) {
+    Ok(pushes) => pushes,
+    // Neither button moves the claw, and the prize is where it starts
+    Err(MathError::NotUnique) => return Some(0),
+    Err(_) => return None,
+  };
+
+  let (low, high) = [(pushes.x, pushes.step_x), (pushes.y, pushes.step_y)]
+    .into_iter()
+    .try_fold(
+      (i64::MIN, i64::MAX),
+      |(low, high), (start, step)| match step.cmp(&0) {
+        Ordering::Greater => Some((low.max(math::div_ceil(-start, step).ok()?), high)),
+        Ordering::Less => Some((low, high.min(math::div_floor(-start, step).ok()?))),
+        Ordering::Equal => (start >= 0).then_some((low, high)),
+      },
+    )?;
+
+  let k = if pushes.step_x * 3 + pushes.step_y >= 0 {
+    low
+  } else {
+    high
+  };
+  if low > high || k == i64::MIN || k == i64::MAX {
+    return None;
+  }
 
-  button_a_pushes * 3 + button_b_pushes
+  let button_a_pushes =
+    math::checked_add(pushes.x, math::checked_mul(k, pushes.step_x).ok()?).ok()?;
+  let button_b_pushes =
+    math::checked_add(pushes.y, math::checked_mul(k, pushes.step_y).ok()?).ok()?;
+  math::checked_add(math::checked_mul(button_a_pushes, 3).ok()?, button_b_pushes).ok()
 }
 
 pub struct Day13 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 13, &Day13 {});
+
 impl AOCDay for Day13 {
+  type Input = Vec<Machine>;
+
   fn name(&self) -> String {
     "day13".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let machines = parse_input(input, false);
-    machines.iter().map(solve_for).sum::<isize>().to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let machines = parse_input(input, true);
+  fn solve_part1(&self, machines: &Self::Input, _params: &Params) -> Answer {
+    total_tokens(machines.iter().copied())
+  }
 
-    let answer = machines.iter().map(solve_for).sum::<isize>();
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let machines = input
+      .iter()
+      .map(|machine| {
+        Ok(Machine {
+          prize: Prize {
+            x: math::checked_add(machine.prize.x, PART_2_PRIZE_OFFSET)?,
+            y: math::checked_add(machine.prize.y, PART_2_PRIZE_OFFSET)?,
+          },
+          ..*machine
+        })
+      })
+      .collect::<Result<Vec<Machine>, MathError>>()
+      .map_err(|err| SolveError::NoSolution(format!("a moved prize {err}")))?;
 
-    answer.to_string()
+    total_tokens(machines.into_iter())
   }
 }
 
@@ -157,12 +214,74 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  #[test]
+  fn test_parallel_buttons() {
+    let machine = |a: (i64, i64), b: (i64, i64), prize: (i64, i64)| Machine {
+      button_a: Button {
+        x_const: a.0,
+        y_const: a.1,
+      },
+      button_b: Button {
+        x_const: b.0,
+        y_const: b.1,
+      },
+      prize: Prize {
+        x: prize.0,
+        y: prize.1,
+      },
+    };
+
+    // Two B pushes is cheaper than any number of A pushes
+    assert_eq!(solve_for(&machine((1, 1), (2, 2), (4, 4))), Ok(Some(2)));
+    // One A push is cheaper than five B pushes
+    assert_eq!(solve_for(&machine((10, 20), (2, 4), (10, 20))), Ok(Some(3)));
+    assert_eq!(solve_for(&machine((6, 6), (4, 4), (5, 5))), Ok(None));
+    assert_eq!(solve_for(&machine((1, 1), (2, 2), (4, 5))), Ok(None));
+    assert_eq!(solve_for(&machine((0, 0), (0, 0), (0, 0))), Ok(Some(0)));
+  }
+
+  #[test]
+  fn test_overflow() {
+    let day = Day13 {};
+    // Winning the prize takes more A pushes than there are tokens to pay for
+    let input = day
+      .parse(&crate::utils::lines(&format!(
+        "Button A: X+1, Y+0\nButton B: X+0, Y+1\nPrize: X={}, Y=0",
+        i64::MAX / 2
+      )))
+      .unwrap();
+    assert_eq!(
+      day.solve_part1(&input, &Params::default()),
+      Err(SolveError::NoSolution(
+        "the tokens needed overflowed an i64".to_string()
+      ))
+    );
+
+    let input = day
+      .parse(&crate::utils::lines(&format!(
+        "Button A: X+1, Y+0\nButton B: X+0, Y+1\nPrize: X={}, Y=0",
+        i64::MAX
+      )))
+      .unwrap();
+    assert_eq!(
+      day.solve_part2(&input, &Params::default()),
+      Err(SolveError::NoSolution(
+        "a moved prize overflowed an i64".to_string()
+      ))
+    );
+  }
+
   #[test]
   fn test_part_1_example() {
     let day = Day13 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day13/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day13/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -171,7 +290,12 @@ mod tests {
     let day = Day13 {};
     assert_eq!(
       "33481",
-      day.solve_part1(&read_file("input/day13/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day13/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -180,7 +304,12 @@ mod tests {
     let day = Day13 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day13/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day13/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -189,7 +318,12 @@ mod tests {
     let day = Day13 {};
     assert_eq!(
       "92572057880885",
-      day.solve_part2(&read_file("input/day13/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day13/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
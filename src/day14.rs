@@ -1,122 +1,150 @@
-use core::panic;
 use regex::Regex;
 use std::collections::HashMap;
+use tracing::debug;
 
+use crate::cancel;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{math, Convert};
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "12";
-const PART_2_EXAMPLE: &str = "SKIP";
 
-struct Robot {
+const WIDTH: isize = 101;
+const HEIGHT: isize = 103;
+
+pub struct Robot {
   px: isize,
   py: isize,
   vx: isize,
   vy: isize,
 }
 
-fn parse_input(input: &[String]) -> Vec<Robot> {
+fn parse_input(input: &[String]) -> Result<Vec<Robot>, SolveError> {
   let robot_regex = Regex::new(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)").unwrap();
 
   input
     .iter()
-    .map(|line| {
-      let capture = robot_regex.captures(line).expect("Could not parse robot");
-      let px = capture
-        .get(1)
-        .unwrap()
-        .as_str()
-        .parse::<isize>()
-        .expect("Could not parse px");
-      let py = capture
-        .get(2)
-        .unwrap()
-        .as_str()
-        .parse::<isize>()
-        .expect("Could not parse py");
-      let vx = capture
-        .get(3)
-        .unwrap()
-        .as_str()
-        .parse::<isize>()
-        .expect("Could not parse vx");
-      let vy = capture
-        .get(4)
-        .unwrap()
-        .as_str()
-        .parse::<isize>()
-        .expect("Could not parse vy");
-
-      Robot { px, py, vx, vy }
+    .enumerate()
+    .map(|(line_idx, line)| {
+      let Some(capture) = robot_regex.captures(line) else {
+        return Err(SolveError::parse(line_idx, "could not parse robot"));
+      };
+      let [px, py, vx, vy] = [1, 2, 3, 4].map(|group| capture[group].parse::<isize>());
+
+      match (px, py, vx, vy) {
+        (Ok(px), Ok(py), Ok(vx), Ok(vy)) => Ok(Robot { px, py, vx, vy }),
+        _ => Err(SolveError::parse(line_idx, "robot values out of range")),
+      }
     })
     .collect()
 }
 
-#[allow(dead_code)]
-fn print_locations(locations: &[(usize, usize)]) {
-  let (width, height): (usize, usize) = match locations.len() {
-    12 => (11, 7),
-    _ => (101, 103),
-  };
-
+/// The number of robots on each tile, or `.` where there are none.
+fn render_locations(locations: &[(usize, usize)], width: usize, height: usize) -> String {
   let mut map = vec![vec![0; width]; height];
 
   for location in locations {
     map[location.1][location.0] += 1;
   }
 
-  for row in &map {
-    for value in row {
-      match value {
-        0 => print!("."),
-        value => print!("{value}"),
-      }
-    }
-    println!();
-  }
-  println!("\n---------------------\n\n");
+  map
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .map(|value| match value {
+          0 => ".".to_string(),
+          value => value.to_string(),
+        })
+        .collect::<String>()
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
 }
 
 fn is_tightly_packed(location: (usize, usize), locations: &HashMap<(usize, usize), isize>) -> bool {
   let mut neighbours = 0;
   if location.0 > 0 && locations.contains_key(&(location.0 - 1, location.1)) {
     neighbours += 1;
-  };
+  }
   if locations.contains_key(&(location.0 + 1, location.1)) {
     neighbours += 1;
-  };
+  }
   if location.1 > 0 && locations.contains_key(&(location.0, location.1 - 1)) {
     neighbours += 1;
-  };
+  }
   if locations.contains_key(&(location.0, location.1 + 1)) {
     neighbours += 1;
-  };
+  }
 
   let count = locations.get(&location).unwrap();
 
   neighbours >= 2 || *count > 1
 }
 
+/// The second in `0..size` when the robots are most bunched up along one axis, given each robot's
+/// starting position and velocity along it. They're drawing the picture along the axis then, and
+/// their positions repeat every `size` seconds.
+fn most_clustered(
+  robots: &[Robot],
+  axis: impl Fn(&Robot) -> (isize, isize),
+  size: isize,
+) -> Result<isize, SolveError> {
+  let cancel = cancel::current();
+  let mut most_clustered = (0, i128::MAX);
+  // `size` comes from the params, so this can be a long loop
+  for seconds in 0..size {
+    cancel.check()?;
+    let positions = robots.iter().map(|robot| {
+      let (position, velocity) = axis(robot);
+      (position + seconds * velocity)
+        .rem_euclid(size)
+        .convert_x::<i128>()
+    });
+    // The variance, scaled up by the square of the number of robots to keep it whole
+    let (count, sum, sum_of_squares) = positions.fold((0, 0, 0), |(count, sum, squares), p| {
+      (count + 1, sum + p, squares + p * p)
+    });
+    let spread = count * sum_of_squares - sum * sum;
+    if spread < most_clustered.1 {
+      most_clustered = (seconds, spread);
+    }
+  }
+  Ok(most_clustered.0)
+}
+
 pub struct Day14 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 14, &Day14 {});
+
 impl AOCDay for Day14 {
+  type Input = Vec<Robot>;
+
   fn name(&self) -> String {
     "day14".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    None
+  }
+
+  fn params(&self) -> &'static [&'static str] {
+    &["width", "height"]
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let robots = parse_input(input);
-    let (width, height) = match robots.len() {
-      12 => (11, 7),
-      _ => (101, 103),
-    };
+  fn solve_part1(&self, robots: &Self::Input, params: &Params) -> Answer {
+    let width = params.get_positive_or("width", WIDTH)?;
+    let height = params.get_positive_or("height", HEIGHT)?;
     let mut results = [0; 4];
 
     let locations = robots
@@ -142,55 +170,58 @@ impl AOCDay for Day14 {
       }
     }
 
-    results.iter().product::<i32>().to_string()
+    Ok(results.iter().product::<i32>().to_string())
   }
 
-  #[allow(clippy::cast_sign_loss)]
-  fn solve_part2(&self, input: &[String]) -> String {
-    let robots = parse_input(input);
-
-    if robots.len() == 12 {
-      // There is no example for part 2
-      return "SKIP".to_string();
-    }
-
-    let width = 101;
-    let height = 103;
+  fn solve_part2(&self, robots: &Self::Input, params: &Params) -> Answer {
+    let width = params.get_positive_or("width", WIDTH)?;
+    let height = params.get_positive_or("height", HEIGHT)?;
+
+    // Each axis repeats on its own period, so the picture is at the second that's the most
+    // clustered second along both
+    let x_seconds = most_clustered(robots, |robot| (robot.px, robot.vx), width)?;
+    let y_seconds = most_clustered(robots, |robot| (robot.py, robot.vy), height)?;
+    let (i, period) = math::crt(&[
+      (x_seconds.convert_x(), width.convert_x()),
+      (y_seconds.convert_x(), height.convert_x()),
+    ])
+    .map_err(|err| SolveError::NoSolution(format!("the axes never line up: {err}")))?;
+    // The robots are back where they started after `period` seconds
+    let i: isize = if i == 0 { period } else { i }.convert_x();
 
-    for i in 1..isize::MAX {
-      let locations = robots
-        .iter()
-        .map(|robot| {
-          let x = (robot.px + i * robot.vx) % width;
-          let y = (robot.py + i * robot.vy) % height;
-
-          let x_result = if x >= 0 { x } else { width + x };
-          let y_result = if y >= 0 { y } else { height + y };
+    let locations = robots
+      .iter()
+      .map(|robot| {
+        let x = (robot.px + i * robot.vx).rem_euclid(width);
+        let y = (robot.py + i * robot.vy).rem_euclid(height);
 
-          (x_result as usize, y_result as usize)
-        })
-        .collect::<Vec<(usize, usize)>>();
+        (x.convert_x::<usize>(), y.convert_x::<usize>())
+      })
+      .collect::<Vec<(usize, usize)>>();
 
-      let mut locations_map = HashMap::new();
-      for location in &locations {
-        *locations_map.entry((location.0, location.1)).or_insert(0) += 1;
-      }
+    let mut locations_map = HashMap::new();
+    for location in &locations {
+      *locations_map.entry((location.0, location.1)).or_insert(0) += 1;
+    }
 
-      let num_tightly_packed = locations
-        .iter()
-        .filter(|location| is_tightly_packed(**location, &locations_map))
-        .count();
-
-      if num_tightly_packed > 300 {
-        // println!("Possible solution found: {i}");
-        // println!("Tightly packed: {num_tightly_packed}");
-        // print_locations(&locations);
-        // println!("---------------------\n\n");
-        return i.to_string();
-      }
+    let num_tightly_packed = locations
+      .iter()
+      .filter(|location| is_tightly_packed(**location, &locations_map))
+      .count();
+
+    debug!(
+      seconds = i,
+      num_tightly_packed,
+      "possible picture\n{}",
+      render_locations(&locations, width.convert_x(), height.convert_x())
+    );
+    if num_tightly_packed > 300 {
+      return Ok(i.to_string());
     }
 
-    panic!("Could not find solution");
+    Err(SolveError::NoSolution(
+      "the robots never form a picture".to_string(),
+    ))
   }
 }
 
@@ -199,12 +230,41 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  fn example_params() -> Params {
+    Params::from([("width", "11"), ("height", "7")])
+  }
+
+  #[test]
+  fn test_invalid_params() {
+    let day = Day14 {};
+    let robots = day.parse(&read_file("input/day14/test1.txt")).unwrap();
+    assert_eq!(
+      day.solve_part2(&robots, &Params::from([("width", "0")])),
+      Err(SolveError::InvalidParam {
+        name: "width".to_string(),
+        value: "0".to_string()
+      })
+    );
+    assert_eq!(
+      day.solve_part1(&robots, &Params::from([("height", "-7")])),
+      Err(SolveError::InvalidParam {
+        name: "height".to_string(),
+        value: "-7".to_string()
+      })
+    );
+  }
+
   #[test]
   fn test_part_1_example() {
     let day = Day14 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day14/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day14/test1.txt")).unwrap(),
+          &example_params()
+        )
+        .unwrap()
     );
   }
 
@@ -213,22 +273,38 @@ mod tests {
     let day = Day14 {};
     assert_eq!(
       "209409792",
-      day.solve_part1(&read_file("input/day14/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day14/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_2_example() {
     let day = Day14 {};
-    assert_eq!(
-      PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day14/test1.txt"))
-    );
+    assert!(matches!(
+      day.solve_part2(
+        &day.parse(&read_file("input/day14/test1.txt")).unwrap(),
+        &example_params()
+      ),
+      Err(SolveError::NoSolution(_))
+    ));
   }
 
   #[test]
   fn test_part_2() {
     let day = Day14 {};
-    assert_eq!("8006", day.solve_part2(&read_file("input/day14/part1.txt")));
+    assert_eq!(
+      "8006",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day14/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
@@ -1,4 +1,10 @@
-use crate::{utils, AOCDay};
+use tracing::{debug, trace};
+
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Convert, Direction, Grid, Point};
+use crate::AOCDay;
 
 // Two inputs. Answers are:
 // 10092 and
@@ -6,288 +12,269 @@ use crate::{utils, AOCDay};
 const PART_1_EXAMPLE: &str = "10092";
 const PART_2_EXAMPLE: &str = "9021";
 
-type Map = Vec<Vec<char>>;
+fn parse_input(input: &[String]) -> Result<(Grid<char>, Vec<Direction>), SolveError> {
+  let Some(empty_line_idx) = input.iter().position(String::is_empty) else {
+    return Err(SolveError::parse(
+      input.len(),
+      "expected a blank line between the map and the moves",
+    ));
+  };
 
-fn parse_input(input: &[String]) -> (Map, String) {
-  let empty_line_idx = input
-    .iter()
-    .enumerate()
-    .find(|(_, line)| line.is_empty())
-    .unwrap()
-    .0;
+  let (map, lines) = input.split_at(empty_line_idx);
 
-  let (map, instructions) = input.split_at(empty_line_idx);
+  let mut instructions = Vec::new();
+  for (line_idx, line) in lines.iter().enumerate() {
+    for (col_idx, char) in line.chars().enumerate() {
+      let instruction = Direction::from_char(char).ok_or_else(|| {
+        SolveError::parse(empty_line_idx + line_idx, "expected one of ^, v, < or >")
+          .at_column(col_idx)
+      })?;
+      instructions.push(instruction);
+    }
+  }
 
-  let map = map.iter().map(|line| line.chars().collect()).collect();
+  let map = Grid::parse(map, |cell| "#.O@".contains(cell).then_some(cell))?;
+  check_map(&map)?;
 
-  (map, instructions.join(""))
+  Ok((map, instructions))
 }
 
-#[allow(dead_code)]
-fn double_map(map: &Map) -> Map {
-  let mut new = vec![vec!['.'; map[0].len() * 2]; map.len()];
+/// Makes sure the map has one robot, and walls all the way round so it can't leave.
+fn check_map(map: &Grid<char>) -> Result<(), SolveError> {
+  let error = |point: Point, message: &str| {
+    SolveError::parse(point.y.convert_x(), message).at_column(point.x.convert_x())
+  };
 
-  for (y, row) in map.iter().enumerate() {
-    for (x, cell) in row.iter().enumerate() {
-      match cell {
-        '.' | '#' => {
-          new[y][x * 2] = *cell;
-          new[y][x * 2 + 1] = *cell;
-        }
-        'O' => {
-          new[y][x * 2] = '[';
-          new[y][x * 2 + 1] = ']';
-        }
-        '@' => {
-          new[y][x * 2] = '@';
-          new[y][x * 2 + 1] = '.';
-        }
-        _ => panic!("Invalid cell"),
-      }
-    }
+  let mut robots = map.iter().filter(|(_, &cell)| cell == '@');
+  if robots.next().is_none() {
+    return Err(SolveError::parse(0, "expected a robot, `@`, on the map"));
+  }
+  if let Some((point, _)) = robots.next() {
+    return Err(error(point, "expected only one robot"));
   }
 
-  new
+  let (right, bottom) = (map.width() - 1, map.height() - 1);
+  let is_edge = |point: Point| {
+    point.x == 0
+      || point.y == 0
+      || point.x.convert_x::<usize>() == right
+      || point.y.convert_x::<usize>() == bottom
+  };
+  match map
+    .iter()
+    .find(|&(point, &cell)| is_edge(point) && cell != '#')
+  {
+    Some((point, _)) => Err(error(point, "expected a wall, `#`, around the map")),
+    None => Ok(()),
+  }
 }
 
-fn find_robot(map: &Map) -> (usize, usize) {
-  for (y, row) in map.iter().enumerate() {
-    for (x, cell) in row.iter().enumerate() {
-      if *cell == '@' {
-        return (x, y);
-      }
-    }
+fn double_map(map: &Grid<char>) -> Grid<char> {
+  let mut new = Grid::new(map.width() * 2, map.height(), '.');
+
+  for (point, cell) in map.iter() {
+    let left = Point {
+      x: point.x * 2,
+      y: point.y,
+    };
+    let right = Point {
+      x: left.x + 1,
+      y: point.y,
+    };
+    let (left_cell, right_cell) = match cell {
+      '.' | '#' => (*cell, *cell),
+      'O' => ('[', ']'),
+      '@' => ('@', '.'),
+      _ => unreachable!("cells are checked by parse_input"),
+    };
+    new[left] = left_cell;
+    new[right] = right_cell;
   }
 
-  panic!("Could not find robot");
+  new
+}
+
+fn find_robot(map: &Grid<char>) -> Result<Point, SolveError> {
+  map
+    .find(&'@')
+    .ok_or_else(|| SolveError::NoSolution("could not find robot".to_string()))
 }
 
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
 fn can_make_move(
-  original_map: &Map,
-  map: &mut Map,
-  instruction: char,
-  current_loc: (usize, usize),
+  original_map: &Grid<char>,
+  map: &mut Grid<char>,
+  instruction: Direction,
+  current_loc: Point,
 ) -> bool {
-  let (x_dir, y_dir): (isize, isize) = match instruction {
-    '^' => (0, -1),
-    'v' => (0, 1),
-    '<' => (-1, 0),
-    '>' => (1, 0),
-    _ => panic!("Invalid instruction"),
-  };
-
-  let new_x = current_loc.0 as isize + x_dir;
-  let new_y = current_loc.1 as isize + y_dir;
-
-  assert!(
-    !(new_x < 0 || new_y < 0 || new_y as usize >= map.len() || new_x as usize >= map[0].len()),
-    "Move out of bounds"
-  );
+  let new_loc = instruction.step(current_loc);
+  assert!(map.contains(new_loc), "Move out of bounds");
 
-  let new_x = utils::isize_to_usize_x(new_x);
-  let new_y = utils::isize_to_usize_x(new_y);
+  let beside = |dx: i32| new_loc + Point::new(dx, 0);
 
-  match original_map[new_y].get(new_x) {
+  match original_map[new_loc] {
     // If empty space, move contents of current location into new location and return true
-    Some('.') => {
-      map[new_y][new_x] = original_map[current_loc.1][current_loc.0];
+    '.' => {
+      map[new_loc] = original_map[current_loc];
       true
     }
-    Some('O') => {
+    'O' => {
       // If box, keep looking along the same direction to
       // see if you find a wall or a space next
-      if can_make_move(original_map, map, instruction, (new_x, new_y)) {
-        map[new_y][new_x] = original_map[current_loc.1][current_loc.0];
+      if can_make_move(original_map, map, instruction, new_loc) {
+        map[new_loc] = original_map[current_loc];
         true
       } else {
         false
       }
     }
-    Some('[') => match instruction {
-      '^' | 'v' => {
-        if can_make_move(original_map, map, instruction, (new_x, new_y))
-          && can_make_move(original_map, map, instruction, (new_x + 1, new_y))
+    '[' => {
+      if instruction.is_vertical() {
+        if can_make_move(original_map, map, instruction, new_loc)
+          && can_make_move(original_map, map, instruction, beside(1))
         {
-          map[new_y][new_x] = original_map[current_loc.1][current_loc.0];
-          if original_map[current_loc.1][current_loc.0] == '@' {
-            map[new_y][new_x + 1] = '.';
+          map[new_loc] = original_map[current_loc];
+          if original_map[current_loc] == '@' {
+            map[beside(1)] = '.';
           }
           true
         } else {
           false
         }
+      } else if can_make_move(original_map, map, instruction, new_loc) {
+        map[new_loc] = original_map[current_loc];
+        true
+      } else {
+        false
       }
-      '<' | '>' => {
-        if can_make_move(original_map, map, instruction, (new_x, new_y)) {
-          map[new_y][new_x] = original_map[current_loc.1][current_loc.0];
-          true
-        } else {
-          false
-        }
-      }
-      _ => panic!("Invalid instruction"),
-    },
-    Some(']') => match instruction {
-      '^' | 'v' => {
-        if can_make_move(original_map, map, instruction, (new_x, new_y))
-          && can_make_move(original_map, map, instruction, (new_x - 1, new_y))
+    }
+    ']' => {
+      if instruction.is_vertical() {
+        if can_make_move(original_map, map, instruction, new_loc)
+          && can_make_move(original_map, map, instruction, beside(-1))
         {
-          map[new_y][new_x] = original_map[current_loc.1][current_loc.0];
-          if original_map[current_loc.1][current_loc.0] == '@' {
-            map[new_y][new_x - 1] = '.';
+          map[new_loc] = original_map[current_loc];
+          if original_map[current_loc] == '@' {
+            map[beside(-1)] = '.';
           }
           true
         } else {
           false
         }
+      } else if can_make_move(original_map, map, instruction, new_loc) {
+        map[new_loc] = map[current_loc];
+        true
+      } else {
+        false
       }
-      '<' | '>' => {
-        if can_make_move(original_map, map, instruction, (new_x, new_y)) {
-          map[new_y][new_x] = map[current_loc.1][current_loc.0];
-          true
-        } else {
-          false
-        }
-      }
-      _ => panic!("Invalid instruction"),
-    },
-    Some('#') => false,
-    _ => panic!("Invalid move"),
-  }
-}
-
-#[allow(dead_code)]
-fn print_map(map: &Map) {
-  for row in map {
-    println!("{}", row.iter().collect::<String>());
+    }
+    '#' => false,
+    _ => unreachable!("cells are checked by parse_input"),
   }
-  println!("----------------\n");
 }
 
-fn is_valid_map(map: &Map) -> bool {
-  let mut robot_count = 0;
-  let mut open_count = 0;
-  let mut close_count = 0;
-
-  for row in map {
-    for cell in row {
-      match cell {
-        '@' => robot_count += 1,
-        '[' => open_count += 1,
-        ']' => close_count += 1,
-        _ => (),
-      }
-    }
-  }
+fn is_valid_map(map: &Grid<char>) -> bool {
+  let count = |value: char| map.iter().filter(|(_, &cell)| cell == value).count();
+  let counts = count('@') == 1 && count('[') == count(']');
 
-  let counts = robot_count == 1 && open_count == close_count;
+  for (point, cell) in map.iter() {
+    let beside = |dx: i32| map.get(point + Point::new(dx, 0));
 
-  for (y, row) in map.iter().enumerate() {
-    for (x, cell) in row.iter().enumerate() {
-      if *cell == '[' && map[y][x + 1] != ']' {
-        return false;
-      }
+    if *cell == '[' && beside(1) != Some(&']') {
+      return false;
+    }
 
-      if *cell == ']' && map[y][x - 1] != '[' {
-        return false;
-      }
+    if *cell == ']' && beside(-1) != Some(&'[') {
+      return false;
     }
   }
 
   counts
 }
 
-fn fix_map(map: &mut Map) {
+fn fix_map(map: &mut Grid<char>) {
   let new_map = map.clone();
-  for (y, row) in new_map.iter().enumerate() {
-    for (x, cell) in row.iter().enumerate() {
-      if *cell == '[' && map[y][x - 1] == '[' {
-        map[y][x - 1] = '.';
-      }
+  for (point, cell) in new_map.iter() {
+    let beside = |dx: i32| point + Point::new(dx, 0);
 
-      if *cell == ']' && map[y][x + 1] == ']' {
-        map[y][x + 1] = '.';
-      }
+    if *cell == '[' && map[beside(-1)] == '[' {
+      map[beside(-1)] = '.';
+    }
+
+    if *cell == ']' && map[beside(1)] == ']' {
+      map[beside(1)] = '.';
     }
   }
 }
 
+/// The sum of the GPS coordinates of every box, found by its `box_cell`.
+fn gps_sum(map: &Grid<char>, box_cell: char) -> i32 {
+  map
+    .iter()
+    .filter(|(_, &cell)| cell == box_cell)
+    .map(|(point, _)| 100 * point.y + point.x)
+    .sum()
+}
+
 pub struct Day15 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 15, &Day15 {});
+
 impl AOCDay for Day15 {
+  type Input = (Grid<char>, Vec<Direction>);
+
   fn name(&self) -> String {
     "day15".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (mut map, instructions) = parse_input(input);
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (mut map, instructions) = input.clone();
 
-    for instruction in instructions.chars() {
-      let robot_loc = find_robot(&map);
+    for &instruction in &instructions {
+      let robot_loc = find_robot(&map)?;
       let mut new_map = map.clone();
       if can_make_move(&map, &mut new_map, instruction, robot_loc) {
-        new_map[robot_loc.1][robot_loc.0] = '.';
+        new_map[robot_loc] = '.';
         map = new_map;
       }
+      trace!("after {instruction}\n{map}");
     }
 
-    let result = map
-      .iter()
-      .enumerate()
-      .flat_map(|(row_idx, row)| {
-        row.iter().enumerate().map(move |(col_idx, cell)| {
-          if *cell == 'O' {
-            100 * row_idx + col_idx
-          } else {
-            0
-          }
-        })
-      })
-      .sum::<usize>();
-    result.to_string()
+    Ok(gps_sum(&map, 'O').to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (start_map, instructions) = parse_input(input);
-    let mut map = double_map(&start_map);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (start_map, instructions) = input;
+    let mut map = double_map(start_map);
 
-    for instruction in instructions.chars() {
-      let robot_loc = find_robot(&map);
+    for &instruction in instructions {
+      let robot_loc = find_robot(&map)?;
       let mut new_map = map.clone();
       if can_make_move(&map, &mut new_map, instruction, robot_loc) {
-        new_map[robot_loc.1][robot_loc.0] = '.';
+        new_map[robot_loc] = '.';
         map = new_map;
         if !is_valid_map(&map) {
           // I feel dirty, but I understand why this hapepens, and it was easier than
           // stopping it happening in the first place!
+          debug!("fixing a box split by {instruction}");
           fix_map(&mut map);
         }
       }
+      trace!("after {instruction}\n{map}");
     }
 
-    let result = map
-      .iter()
-      .enumerate()
-      .flat_map(|(row_idx, row)| {
-        row.iter().enumerate().map(move |(col_idx, cell)| {
-          if *cell == '[' {
-            100 * row_idx + col_idx
-          } else {
-            0
-          }
-        })
-      })
-      .sum::<usize>();
-    result.to_string()
+    Ok(gps_sum(&map, '[').to_string())
   }
 }
 
@@ -296,12 +283,35 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  #[test]
+  fn test_parse_errors() {
+    let parse = |input: &str| parse_input(&crate::utils::lines(input));
+    assert_eq!(
+      parse("###\n#x#\n###\n\n<").err(),
+      Some(SolveError::parse(1, "unexpected character 'x'").at_column(1))
+    );
+    assert_eq!(
+      parse("####\n#@@#\n####\n\n<").err(),
+      Some(SolveError::parse(1, "expected only one robot").at_column(2))
+    );
+    assert_eq!(
+      parse("###\n#@.\n###\n\n<").err(),
+      Some(SolveError::parse(1, "expected a wall, `#`, around the map").at_column(2))
+    );
+    assert!(parse("###\n#@#\n###\n\n<").is_ok());
+  }
+
   #[test]
   fn test_part_1_example() {
     let day = Day15 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day15/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day15/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -310,7 +320,12 @@ mod tests {
     let day = Day15 {};
     assert_eq!(
       "1294459",
-      day.solve_part1(&read_file("input/day15/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day15/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -319,7 +334,12 @@ mod tests {
     let day = Day15 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day15/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day15/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -328,7 +348,12 @@ mod tests {
     let day = Day15 {};
     assert_eq!(
       "1319212",
-      day.solve_part2(&read_file("input/day15/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day15/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -1,165 +1,116 @@
 use pathfinding::prelude::astar;
 use pathfinding::prelude::astar_bag;
 use std::collections::HashSet;
+use strum::IntoEnumIterator;
 
-use crate::utils;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Direction, Grid, Point};
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "11048";
 const PART_2_EXAMPLE: &str = "64";
 
-type Map = Vec<Vec<char>>;
-type Location = (i32, i32);
-
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 pub struct Reindeer {
-  location: Location,
-  direction: (i32, i32),
+  location: Point,
+  direction: Direction,
 }
 
-#[allow(dead_code)]
-fn print_map(map: &Map) {
-  for row in map {
-    println!("{}", row.iter().collect::<String>());
-  }
-  println!("\n----------------\n\n");
-}
-
-fn parse_input(input: &[String]) -> (Map, Location, Location) {
-  let map = input
-    .iter()
-    .map(|line| line.chars().collect())
-    .collect::<Vec<Vec<char>>>();
+fn parse_input(input: &[String]) -> Result<(Grid<char>, Point, Point), SolveError> {
+  let map = Grid::parse(input, Some)?;
 
   let start_position = map
-    .iter()
-    .enumerate()
-    .find_map(|(y, row)| {
-      row.iter().enumerate().find_map(|(x, &c)| {
-        if c == 'S' {
-          Some((
-            utils::usize_to_i32(x).unwrap(),
-            utils::usize_to_i32(y).unwrap(),
-          ))
-        } else {
-          None
-        }
-      })
-    })
-    .unwrap_or_else(|| panic!("No start position found"));
+    .find(&'S')
+    .ok_or_else(|| SolveError::parse(0, "expected a start, `S`, on the map"))?;
 
   let end_position = map
-    .iter()
-    .enumerate()
-    .find_map(|(y, row)| {
-      row.iter().enumerate().find_map(|(x, &c)| {
-        if c == 'E' {
-          Some((
-            utils::usize_to_i32(x).unwrap(),
-            utils::usize_to_i32(y).unwrap(),
-          ))
-        } else {
-          None
-        }
-      })
-    })
-    .unwrap_or_else(|| panic!("No end position found"));
+    .find(&'E')
+    .ok_or_else(|| SolveError::parse(0, "expected an end, `E`, on the map"))?;
 
-  (map, start_position, end_position)
+  Ok((map, start_position, end_position))
 }
 
-fn get_neighbours(map: &Map, reindeer: &Reindeer) -> Vec<(Reindeer, i32)> {
-  let mut neighbours = vec![];
-
-  for (dx, dy) in &[(0, -1), (0, 1), (-1, 0), (1, 0)] {
-    let new_pos = (reindeer.location.0 + dx, reindeer.location.1 + dy);
-    if new_pos.1 >= 0
-      && new_pos.1 < utils::usize_to_i32(map.len()).unwrap()
-      && new_pos.0 >= 0
-      && new_pos.0 < utils::usize_to_i32(map[0].len()).unwrap()
-      && map[utils::i32_to_usize(new_pos.1).unwrap()][utils::i32_to_usize(new_pos.0).unwrap()]
-        != '#'
-    {
-      let cost = if (*dx, *dy) == reindeer.direction {
+fn get_neighbours(map: &Grid<char>, reindeer: &Reindeer) -> Vec<(Reindeer, i32)> {
+  Direction::iter()
+    .filter_map(|direction| {
+      let location = direction.step_within(reindeer.location, map.width(), map.height())?;
+      (map[location] != '#').then_some((location, direction))
+    })
+    .map(|(location, direction)| {
+      let cost = if direction == reindeer.direction {
         1
       } else {
         // Note: If we turn 180 degrees this should be 2001
         // But we don't account for this as turning back on yourself will never be the best option
         1001
       };
-      neighbours.push((
+      (
         Reindeer {
-          location: new_pos,
-          direction: (*dx, *dy),
+          location,
+          direction,
         },
         cost,
-      ));
-    }
-  }
-
-  neighbours
+      )
+    })
+    .collect()
 }
 
 pub struct Day16 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 16, &Day16 {});
+
 impl AOCDay for Day16 {
+  type Input = (Grid<char>, Point, Point);
+
   fn name(&self) -> String {
     "day16".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (map, start_position, end_position) = parse_input(input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let &(ref map, start_position, end_position) = input;
 
     let reindeer = Reindeer {
       location: start_position,
-      direction: (1, 0), // East
+      direction: Direction::Right,
     };
 
     match astar(
       &reindeer,
-      |reindeer| get_neighbours(&map, reindeer),
-      |reindeer| {
-        utils::u32_to_i32(
-          end_position.0.abs_diff(reindeer.location.0)
-            + end_position.1.abs_diff(reindeer.location.1),
-        )
-        .unwrap()
-      },
+      |reindeer| get_neighbours(map, reindeer),
+      |reindeer| reindeer.location.manhattan_distance(end_position),
       |reindeer| reindeer.location == end_position,
     ) {
-      Some((_, cost)) => cost.to_string(),
-      None => {
-        panic!("No path found.");
-      }
+      Some((_, cost)) => Ok(cost.to_string()),
+      None => Err(SolveError::NoSolution("no path found".to_string())),
     }
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (map, start_position, end_position) = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let &(ref map, start_position, end_position) = input;
 
     let reindeer = Reindeer {
       location: start_position,
-      direction: (1, 0), // East
+      direction: Direction::Right,
     };
 
     match astar_bag(
       &reindeer,
-      |reindeer| get_neighbours(&map, reindeer),
-      |reindeer| {
-        utils::u32_to_i32(
-          end_position.0.abs_diff(reindeer.location.0)
-            + end_position.1.abs_diff(reindeer.location.1),
-        )
-        .unwrap()
-      },
+      |reindeer| get_neighbours(map, reindeer),
+      |reindeer| reindeer.location.manhattan_distance(end_position),
       |reindeer| reindeer.location == end_position,
     ) {
       Some((solution, _)) => {
@@ -168,11 +119,9 @@ impl AOCDay for Day16 {
           visited.insert(reindeer.location);
         });
         let result = visited.len();
-        result.to_string()
-      }
-      None => {
-        panic!("No path found.");
+        Ok(result.to_string())
       }
+      None => Err(SolveError::NoSolution("no path found".to_string())),
     }
   }
 }
@@ -180,14 +129,31 @@ impl AOCDay for Day16 {
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::utils::read_file;
+  use crate::utils::{lines, read_file};
+
+  #[test]
+  fn test_parse_errors() {
+    assert_eq!(
+      parse_input(&lines("#.E#")).err(),
+      Some(SolveError::parse(0, "expected a start, `S`, on the map"))
+    );
+    assert_eq!(
+      parse_input(&lines("#S.#")).err(),
+      Some(SolveError::parse(0, "expected an end, `E`, on the map"))
+    );
+  }
 
   #[test]
   fn test_part_1_example() {
     let day = Day16 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day16/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day16/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -196,7 +162,12 @@ mod tests {
     let day = Day16 {};
     assert_eq!(
       "101492",
-      day.solve_part1(&read_file("input/day16/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day16/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -205,13 +176,26 @@ mod tests {
     let day = Day16 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day16/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day16/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day16 {};
-    assert_eq!("543", day.solve_part2(&read_file("input/day16/part1.txt")));
+    assert_eq!(
+      "543",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day16/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
@@ -1,48 +1,75 @@
 use regex::Regex;
 use std::string::ToString;
 
-use crate::{utils, AOCDay};
+use crate::cancel;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::Convert;
+use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "4,6,3,5,6,3,5,2,1,0";
 const PART_2_EXAMPLE: &str = "117440";
 
 #[derive(Clone)]
-struct Computer {
+pub struct Computer {
   reg_a: u64,
   reg_b: u64,
   reg_c: u64,
   instruction_ptr: u32,
 }
 
-fn parse_input(input: &[String]) -> (Computer, Vec<u8>) {
-  let regex = Regex::new(r"^Register [A|B|C]: (\d+)$").unwrap();
-  let Some(caps_a) = regex.captures(&input[0]) else {
-    panic!("Failed to parse first line");
-  };
-  let Some(caps_b) = regex.captures(&input[1]) else {
-    panic!("Failed to parse second line");
-  };
-  let Some(caps_c) = regex.captures(&input[2]) else {
-    panic!("Failed to parse third line");
+fn parse_register(regex: &Regex, input: &[String], line_idx: usize) -> Result<u64, SolveError> {
+  let line = input.get(line_idx).map_or("", String::as_str);
+  let Some(caps) = regex.captures(line) else {
+    return Err(SolveError::parse(line_idx, "expected a register"));
   };
 
-  let reg_a = caps_a.get(1).unwrap().as_str().parse::<u64>().unwrap();
-  let reg_b = caps_b.get(1).unwrap().as_str().parse::<u64>().unwrap();
-  let reg_c = caps_c.get(1).unwrap().as_str().parse::<u64>().unwrap();
+  caps[1]
+    .parse::<u64>()
+    .map_err(|msg| SolveError::parse(line_idx, format!("failed to parse register: {msg}")))
+}
+
+fn parse_input(input: &[String]) -> Result<(Computer, Vec<u8>), SolveError> {
+  let regex = Regex::new(r"^Register [A|B|C]: (\d+)$").unwrap();
+  let reg_a = parse_register(&regex, input, 0)?;
+  let reg_b = parse_register(&regex, input, 1)?;
+  let reg_c = parse_register(&regex, input, 2)?;
 
   let program_regex = Regex::new(r"Program: ([0-9,]+)").unwrap();
-  let Some(caps_program) = program_regex.captures(&input[4]) else {
-    panic!("Failed to parse program line");
+  let program_line = input.get(4).map_or("", String::as_str);
+  let Some(values) = program_regex
+    .captures(program_line)
+    .and_then(|caps| caps.get(1))
+  else {
+    return Err(SolveError::parse(4, "expected the program"));
   };
-  let program = caps_program
-    .get(1)
-    .unwrap()
-    .as_str()
-    .split(',')
-    .map(|x| x.parse::<u8>().unwrap())
-    .collect();
-
-  (
+
+  let mut program = Vec::new();
+  let mut col_idx = values.start();
+  for text in values.as_str().split(',') {
+    let value = text
+      .parse::<u8>()
+      .map_err(|msg| SolveError::parse(4, format!("failed to parse program: {msg}")))
+      .and_then(|value| {
+        match program.len() % 2 {
+          0 => check_opcode(value),
+          _ => check_operand(program[program.len() - 1], value),
+        }
+        .map(|()| value)
+        .map_err(|msg| SolveError::parse(4, msg))
+      })
+      .map_err(|err| err.at_column(col_idx))?;
+    col_idx += text.len() + 1;
+    program.push(value);
+  }
+  if program.len() % 2 == 1 {
+    return Err(
+      SolveError::parse(4, "expected an operand after the last opcode").at_column(col_idx - 1),
+    );
+  }
+
+  Ok((
     Computer {
       reg_a,
       reg_b,
@@ -50,7 +77,33 @@ fn parse_input(input: &[String]) -> (Computer, Vec<u8>) {
       instruction_ptr: 0,
     },
     program,
-  )
+  ))
+}
+
+fn check_opcode(opcode: u8) -> Result<(), String> {
+  if opcode > 7 {
+    return Err(format!("{opcode} is not an opcode"));
+  }
+  Ok(())
+}
+
+fn check_operand(opcode: u8, operand: u8) -> Result<(), String> {
+  match opcode {
+    // Combo operand 7 is reserved
+    0 | 2 | 5 | 6 | 7 if operand > 6 => Err(format!("{operand} is not a combo operand")),
+    // Jumping to an odd address would read the operands as opcodes
+    3 if operand % 2 == 1 => Err(format!("{operand} is in the middle of an instruction")),
+    _ if operand > 7 => Err(format!("{operand} is not a 3-bit operand")),
+    _ => Ok(()),
+  }
+}
+
+/// `numerator / 2^exponent`, which is 0 once the exponent is past the width of a `u64`.
+fn divide(numerator: u64, exponent: u64) -> u64 {
+  u32::try_from(exponent)
+    .ok()
+    .and_then(|exponent| numerator.checked_shr(exponent))
+    .unwrap_or(0)
 }
 
 fn combo_operand(operand: u8, computer: &Computer) -> u64 {
@@ -62,15 +115,14 @@ fn combo_operand(operand: u8, computer: &Computer) -> u64 {
     4 => computer.reg_a,
     5 => computer.reg_b,
     6 => computer.reg_c,
-    7 => panic!("Reserved. Impossible"),
-    _ => panic!("Invalid operand"),
+    _ => unreachable!("operands are checked by parse_input"),
   }
 }
 
 fn perform_opcode(opcode: u8, operand: u8, computer: &mut Computer) -> Option<String> {
   match opcode {
     0 => {
-      computer.reg_a /= 2_u64.pow(utils::u64_to_u32_x(combo_operand(operand, computer)));
+      computer.reg_a = divide(computer.reg_a, combo_operand(operand, computer));
       computer.instruction_ptr += 2;
     }
     1 => {
@@ -99,44 +151,50 @@ fn perform_opcode(opcode: u8, operand: u8, computer: &mut Computer) -> Option<St
       return Some(format!("{output}"));
     }
     6 => {
-      computer.reg_b =
-        computer.reg_a / 2_u64.pow(utils::u64_to_u32_x(combo_operand(operand, computer)));
+      computer.reg_b = divide(computer.reg_a, combo_operand(operand, computer));
       computer.instruction_ptr += 2;
     }
     7 => {
-      computer.reg_c =
-        computer.reg_a / 2_u64.pow(utils::u64_to_u32_x(combo_operand(operand, computer)));
+      computer.reg_c = divide(computer.reg_a, combo_operand(operand, computer));
       computer.instruction_ptr += 2;
     }
 
-    _ => panic!("Invalid opcode"),
+    _ => unreachable!("opcodes are checked by parse_input"),
   }
 
   None
 }
 
-fn run_program(program: &[u8], computer: &mut Computer) -> Vec<String> {
+fn run_program(program: &[u8], computer: &mut Computer) -> Result<Vec<String>, SolveError> {
+  let cancel = cancel::current();
   let mut output = Vec::new();
 
-  while computer.instruction_ptr < utils::usize_to_u32_x(program.len()) {
-    let opcode = program[utils::u32_to_usize_x(computer.instruction_ptr)];
-    let operand = program[utils::u32_to_usize_x(computer.instruction_ptr) + 1];
+  // A program can jump back forever
+  while computer.instruction_ptr < program.len().convert_x() {
+    cancel.check()?;
+    let opcode = program[computer.instruction_ptr.convert_x::<usize>()];
+    let operand = program[computer.instruction_ptr.convert_x::<usize>() + 1];
     if let Some(oput) = perform_opcode(opcode, operand, computer) {
       output.push(oput);
     }
   }
 
-  output
+  Ok(output)
 }
 
-fn recurse_part_2(program: &[u8], computer: &Computer, index: usize, a: u64) -> Option<u64> {
+fn recurse_part_2(
+  program: &[u8],
+  computer: &Computer,
+  index: usize,
+  a: u64,
+) -> Result<Option<u64>, SolveError> {
   for candidate in 0..8 {
     let next_a = a * 8 + candidate;
     let mut next_computer = computer.clone();
     next_computer.reg_a = next_a;
     next_computer.reg_b = 0;
     next_computer.reg_c = 0;
-    let output = run_program(program, &mut next_computer);
+    let output = run_program(program, &mut next_computer)?;
     let (_, suffix) = program.split_at(index);
     if output
       == suffix
@@ -145,39 +203,47 @@ fn recurse_part_2(program: &[u8], computer: &Computer, index: usize, a: u64) ->
         .collect::<Vec<String>>()
     {
       if index == 0 {
-        return Some(next_a);
+        return Ok(Some(next_a));
       }
-      let next = recurse_part_2(program, computer, index - 1, next_a);
+      let next = recurse_part_2(program, computer, index - 1, next_a)?;
       if next.is_some() {
-        return next;
+        return Ok(next);
       }
     }
   }
 
-  None
+  Ok(None)
 }
 
 pub struct Day17 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 17, &Day17 {});
+
 impl AOCDay for Day17 {
+  type Input = (Computer, Vec<u8>);
+
   fn name(&self) -> String {
     "day17".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (mut computer, program) = parse_input(input);
-    let output = run_program(&program, &mut computer);
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (mut computer, program) = input.clone();
+    let output = run_program(&program, &mut computer)?;
     let result = output.join(",");
 
-    result.to_string()
+    Ok(result)
   }
 
   /**
@@ -191,12 +257,13 @@ impl AOCDay for Day17 {
    *   out.add(b % 8)
    * }
    */
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (computer, program) = parse_input(input);
-    if let Some(result) = recurse_part_2(&program, &computer, program.len() - 1, 0) {
-      result.to_string()
-    } else {
-      panic!("Failed to find a solution");
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (computer, program) = input;
+    match recurse_part_2(program, computer, program.len() - 1, 0)? {
+      Some(result) => Ok(result.to_string()),
+      None => Err(SolveError::NoSolution(
+        "no value of register A outputs the program".to_string(),
+      )),
     }
   }
 }
@@ -205,6 +272,8 @@ impl AOCDay for Day17 {
 mod tests {
   use super::*;
   use crate::utils::read_file;
+  use std::sync::mpsc;
+  use std::time::Duration;
 
   #[test]
   fn test_adv_1() {
@@ -285,7 +354,7 @@ mod tests {
       instruction_ptr: 0,
     };
     let program: Vec<u8> = vec![5, 0, 5, 1, 5, 4];
-    let output = run_program(&program, &mut computer);
+    let output = run_program(&program, &mut computer).unwrap();
 
     assert!(output[0] == "0");
     assert!(output[1] == "1");
@@ -302,7 +371,7 @@ mod tests {
       instruction_ptr: 0,
     };
     let program: Vec<u8> = vec![0, 1, 5, 4, 3, 0];
-    let output = run_program(&program, &mut computer);
+    let output = run_program(&program, &mut computer).unwrap();
     let result = output.join(",");
 
     assert!(computer.reg_a == 0);
@@ -318,7 +387,7 @@ mod tests {
       instruction_ptr: 0,
     };
     let program: Vec<u8> = vec![1, 7];
-    run_program(&program, &mut computer);
+    run_program(&program, &mut computer).unwrap();
 
     assert!(computer.reg_b == 26);
   }
@@ -332,11 +401,67 @@ mod tests {
       instruction_ptr: 0,
     };
     let program: Vec<u8> = vec![4, 0];
-    run_program(&program, &mut computer);
+    run_program(&program, &mut computer).unwrap();
 
     assert!(computer.reg_b == 44354);
   }
 
+  #[test]
+  fn test_parse_errors() {
+    let parse = |program: &str| {
+      parse_input(&crate::utils::lines(&format!(
+        "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}"
+      )))
+    };
+    assert_eq!(
+      parse("8,0").err(),
+      Some(SolveError::parse(4, "8 is not an opcode").at_column(9))
+    );
+    assert_eq!(
+      parse("0,1,5,7").err(),
+      Some(SolveError::parse(4, "7 is not a combo operand").at_column(15))
+    );
+    assert_eq!(
+      parse("3,1").err(),
+      Some(SolveError::parse(4, "1 is in the middle of an instruction").at_column(11))
+    );
+    assert_eq!(
+      parse("0,1,5").err(),
+      Some(SolveError::parse(4, "expected an operand after the last opcode").at_column(14))
+    );
+    assert!(parse("1,7,3,0").is_ok());
+  }
+
+  #[test]
+  fn test_divide() {
+    assert_eq!(divide(729, 1), 364);
+    assert_eq!(divide(729, 64), 0);
+    assert_eq!(divide(729, u64::MAX), 0);
+  }
+
+  #[test]
+  fn test_endless_program_is_cancelled() {
+    // Jumps back to the start for as long as A isn't 0, which it never stops being
+    let input = parse_input(&crate::utils::lines(
+      "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0",
+    ))
+    .unwrap();
+    let limit = Duration::from_millis(50);
+    let (sender, receiver) = mpsc::channel();
+    let result = cancel::run_with_timeout(limit, move || {
+      let result = Day17 {}.solve_part1(&input, &Params::default());
+      sender.send(result.clone()).unwrap();
+      result
+    });
+
+    assert_eq!(result, Err(SolveError::TimedOut(limit)));
+    // The solver's thread stopped rather than spinning on in the background
+    assert_eq!(
+      receiver.recv_timeout(Duration::from_secs(10)),
+      Ok(Err(SolveError::Cancelled))
+    );
+  }
+
   /////////
 
   #[test]
@@ -344,7 +469,12 @@ mod tests {
     let day = Day17 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day17/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day17/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -353,7 +483,12 @@ mod tests {
     let day = Day17 {};
     assert_eq!(
       "5,1,3,4,3,7,2,1,7",
-      day.solve_part1(&read_file("input/day17/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day17/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -362,7 +497,12 @@ mod tests {
     let day = Day17 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day17/test2.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day17/test2.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -371,7 +511,12 @@ mod tests {
     let day = Day17 {};
     assert_eq!(
       "216584205979245",
-      day.solve_part2(&read_file("input/day17/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day17/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -1,170 +1,136 @@
-use core::panic;
+use tracing::debug;
 
-use crate::{utils, AOCDay};
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Convert, Grid, ParsePointError, Point};
+use crate::AOCDay;
 use pathfinding::prelude::astar;
 
 const PART_1_EXAMPLE: &str = "22";
 const PART_2_EXAMPLE: &str = "6,1";
 
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-struct Point {
-  x: i32,
-  y: i32,
-}
-
-impl std::fmt::Display for Point {
-  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-    write!(f, "{},{}", self.x, self.y)
-  }
-}
-
-type Map = Vec<Vec<char>>;
+/// The memory space is SIZE x SIZE, and part 1 looks at it after BYTES bytes have fallen
+const SIZE: usize = 71;
+const BYTES: usize = 1024;
 
-#[allow(dead_code)]
-fn print_map(map: &Map) {
-  for row in map {
-    println!("{}", row.iter().collect::<String>());
-  }
-  println!("----------------\n");
-}
-
-fn parse_input(input: &[String]) -> Vec<Point> {
+fn parse_input(input: &[String]) -> Result<Vec<Point>, SolveError> {
   input
     .iter()
-    .map(|line| {
-      let split = line.split(',').collect::<Vec<&str>>();
-      Point {
-        x: split[0].parse().unwrap(),
-        y: split[1].parse().unwrap(),
-      }
+    .enumerate()
+    .map(|(line_idx, line)| {
+      line
+        .parse()
+        .map_err(|err: ParsePointError| SolveError::parse(line_idx, err.to_string()))
     })
     .collect()
 }
 
-fn is_test(input: &[Point]) -> bool {
-  input.len() == 25
+fn get_neighbours(map: &Grid<char>, location: Point) -> Vec<(Point, i32)> {
+  map
+    .neighbours(location)
+    .filter(|&neighbour| map[neighbour] != '#')
+    .map(|neighbour| (neighbour, 1))
+    .collect()
 }
 
-fn get_neighbours(map: &Map, location: Point) -> Vec<(Point, i32)> {
-  let mut neighbours = vec![];
-
-  for (dx, dy) in &[(0, -1), (0, 1), (-1, 0), (1, 0)] {
-    let new_pos = (location.x + dx, location.y + dy);
-    let col_map = utils::usize_to_i32_x(map[0].len());
-    let row_max = utils::usize_to_i32_x(map.len());
-
-    if new_pos.1 >= 0
-      && new_pos.1 < row_max
-      && new_pos.0 >= 0
-      && new_pos.0 < col_map
-      && map[utils::i32_to_usize_x(new_pos.1)][utils::i32_to_usize_x(new_pos.0)] != '#'
-    {
-      neighbours.push((
-        Point {
-          x: new_pos.0,
-          y: new_pos.1,
-        },
-        1,
-      ));
-    }
+/// The memory space after the first `num_bytes` bytes have fallen
+fn corrupt(bytes: &[Point], grid_dim: usize, num_bytes: usize) -> Result<Grid<char>, SolveError> {
+  let mut map = Grid::new(grid_dim, grid_dim, '.');
+
+  for (line_idx, next_byte) in bytes.iter().enumerate().take(num_bytes) {
+    drop_byte(&mut map, line_idx, *next_byte)?;
   }
 
-  neighbours
+  Ok(map)
+}
+
+/// Corrupts the memory where the byte on line `line_idx` of the input falls.
+fn drop_byte(map: &mut Grid<char>, line_idx: usize, byte: Point) -> Result<(), SolveError> {
+  let cell = map
+    .get_mut(byte)
+    .ok_or_else(|| SolveError::parse(line_idx, "byte outside the grid"))?;
+  *cell = '#';
+  Ok(())
 }
 
 pub struct Day18 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 18, &Day18 {});
+
 impl AOCDay for Day18 {
+  type Input = Vec<Point>;
+
   fn name(&self) -> String {
     "day18".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    let is_test_run = is_test(&input);
-
-    let grid_dim = if is_test_run { 7 } else { 71 };
-    let num_steps = if is_test_run { 12 } else { 1024 };
+  fn params(&self) -> &'static [&'static str] {
+    &["size", "bytes"]
+  }
 
-    let mut map = vec![vec!['.'; grid_dim]; grid_dim];
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
 
-    for next_byte in input.iter().take(num_steps) {
-      let col_idx = utils::i32_to_usize(next_byte.x).unwrap();
-      let row_idx = utils::i32_to_usize(next_byte.y).unwrap();
+  fn solve_part1(&self, input: &Self::Input, params: &Params) -> Answer {
+    let grid_dim = params.get_positive_or("size", SIZE)?;
+    let num_steps = params.get_or("bytes", BYTES)?;
 
-      map[row_idx][col_idx] = '#';
-    }
+    let map = corrupt(input, grid_dim, num_steps)?;
 
-    let start = Point { x: 0, y: 0 };
-    let end = Point {
-      x: utils::usize_to_i32_x(grid_dim - 1),
-      y: utils::usize_to_i32_x(grid_dim - 1),
-    };
+    let start = Point::new(0, 0);
+    let corner = (grid_dim - 1).convert_x();
+    let end = Point::new(corner, corner);
 
     match astar(
       &start,
       |location| get_neighbours(&map, *location),
-      |location| {
-        utils::u32_to_i32(end.x.abs_diff(location.x) + end.y.abs_diff(location.y)).unwrap()
-      },
+      |location| location.manhattan_distance(end),
       |location| location == &end,
     ) {
-      Some((_, cost)) => cost.to_string(),
-      None => {
-        panic!("No path found.");
-      }
+      Some((_, cost)) => Ok(cost.to_string()),
+      None => Err(SolveError::NoSolution("no path found".to_string())),
     }
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    let is_test_run = is_test(&input);
+  fn solve_part2(&self, input: &Self::Input, params: &Params) -> Answer {
+    let grid_dim = params.get_positive_or("size", SIZE)?;
+    let num_steps = params.get_or("bytes", BYTES)?;
 
-    let grid_dim = if is_test_run { 7 } else { 71 };
-    let num_steps = if is_test_run { 12 } else { 1024 };
+    let mut map = corrupt(input, grid_dim, num_steps)?;
 
-    let mut map = vec![vec!['.'; grid_dim]; grid_dim];
-
-    for next_byte in input.iter().take(num_steps) {
-      let col_idx = utils::i32_to_usize(next_byte.x).unwrap();
-      let row_idx = utils::i32_to_usize(next_byte.y).unwrap();
-
-      map[row_idx][col_idx] = '#';
-    }
-
-    let start = Point { x: 0, y: 0 };
-    let end = Point {
-      x: utils::usize_to_i32_x(grid_dim - 1),
-      y: utils::usize_to_i32_x(grid_dim - 1),
-    };
+    let start = Point::new(0, 0);
+    let corner = (grid_dim - 1).convert_x();
+    let end = Point::new(corner, corner);
 
     for (step, next_byte) in input.iter().enumerate().take(input.len()).skip(num_steps) {
-      let col_idx = utils::i32_to_usize(next_byte.x).unwrap();
-      let row_idx = utils::i32_to_usize(next_byte.y).unwrap();
-      map[row_idx][col_idx] = '#';
+      drop_byte(&mut map, step, *next_byte)?;
 
       if let Some((_, _)) = astar(
         &start,
         |location| get_neighbours(&map, *location),
-        |location| {
-          utils::u32_to_i32(end.x.abs_diff(location.x) + end.y.abs_diff(location.y)).unwrap()
-        },
+        |location| location.manhattan_distance(end),
         |location| location == &end,
       ) {
         {}
       } else {
-        return input[step].to_string();
+        debug!(step, "{} blocks the path\n{map}", input[step]);
+        return Ok(input[step].to_string());
       }
     }
-    panic!("No blocked path found.");
+
+    Err(SolveError::NoSolution(
+      "no byte blocks the path".to_string(),
+    ))
   }
 }
 
@@ -173,19 +139,49 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  #[test]
+  fn test_custom_sizes() {
+    let day = Day18 {};
+    let input = day.parse(&read_file("input/day18/test1.txt")).unwrap();
+    assert_eq!(
+      day.solve_part1(&input, &Params::from([("size", "5"), ("bytes", "12")])),
+      Err(SolveError::parse(0, "byte outside the grid"))
+    );
+    assert_eq!(
+      day.solve_part1(&input, &Params::from([("size", "0")])),
+      Err(SolveError::InvalidParam {
+        name: "size".to_string(),
+        value: "0".to_string()
+      })
+    );
+  }
+
   #[test]
   fn test_part_1_example() {
     let day = Day18 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day18/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day18/test1.txt")).unwrap(),
+          &Params::from([("size", "7"), ("bytes", "12")])
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day18 {};
-    assert_eq!("356", day.solve_part1(&read_file("input/day18/part1.txt")));
+    assert_eq!(
+      "356",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day18/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
@@ -193,7 +189,12 @@ mod tests {
     let day = Day18 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day18/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day18/test1.txt")).unwrap(),
+          &Params::from([("size", "7"), ("bytes", "12")])
+        )
+        .unwrap()
     );
   }
 
@@ -202,7 +203,12 @@ mod tests {
     let day = Day18 {};
     assert_eq!(
       "22,33",
-      day.solve_part2(&read_file("input/day18/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day18/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -1,3 +1,7 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::parse;
 use crate::AOCDay;
 use memoize::memoize;
 use std::string::String;
@@ -5,27 +9,33 @@ use std::string::String;
 const PART_1_EXAMPLE: &str = "6";
 const PART_2_EXAMPLE: &str = "16";
 
-fn parse_input(input: &[String]) -> (Vec<&str>, Vec<String>) {
-  let mut splits = input.split(String::is_empty);
-  let towels = splits
-    .next()
-    .unwrap()
+fn parse_input(input: &[String]) -> Result<(Vec<String>, Vec<String>), SolveError> {
+  let mut sections = parse::sections(input);
+  let (Some((_, towels)), Some((_, designs))) = (sections.next(), sections.next()) else {
+    return Err(SolveError::parse(
+      input.len(),
+      "expected a blank line between the towels and the designs",
+    ));
+  };
+
+  let towels = towels
     .iter()
-    .flat_map(|line| line.split(", "))
-    .collect::<Vec<&str>>();
-  let designs = splits.next().unwrap().to_vec();
+    .enumerate()
+    .map(|(line_idx, line)| parse::list::<String>(line_idx, line, ","))
+    .collect::<Result<Vec<Vec<String>>, SolveError>>()?
+    .concat();
 
-  (towels, designs)
+  Ok((towels, designs.to_vec()))
 }
 
-fn is_possible(towels: &[&str], design: &str) -> bool {
+fn is_possible(towels: &[String], design: &str) -> bool {
   let design_length = design.len();
   let mut matches = vec![false; design_length + 1];
   matches[0] = true;
 
   for i in 1..=design_length {
     for towel in towels {
-      if towel.len() <= i && *towel == &design[i - towel.len()..i] && matches[i - towel.len()] {
+      if towel.len() <= i && towel == &design[i - towel.len()..i] && matches[i - towel.len()] {
         matches[i] = true;
         break;
       }
@@ -54,41 +64,45 @@ fn valid_paths(towels: Vec<String>, design: String) -> usize {
 
 pub struct Day19 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 19, &Day19 {});
+
 impl AOCDay for Day19 {
+  type Input = (Vec<String>, Vec<String>);
+
   fn name(&self) -> String {
     "day19".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (towels, designs) = parse_input(input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (towels, designs) = input;
 
     let result = designs
       .iter()
-      .filter(|design| is_possible(&towels, design.as_str()))
+      .filter(|design| is_possible(towels, design.as_str()))
       .count();
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (towels, designs) = parse_input(input);
-    let towels = towels
-      .iter()
-      .map(ToString::to_string)
-      .collect::<Vec<String>>();
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (towels, designs) = input;
 
     let result = designs
       .iter()
       .map(|design| valid_paths(towels.clone(), design.clone()))
       .sum::<usize>();
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
@@ -102,14 +116,27 @@ mod tests {
     let day = Day19 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day19/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day19/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day19 {};
-    assert_eq!("290", day.solve_part1(&read_file("input/day19/part1.txt")));
+    assert_eq!(
+      "290",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day19/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
@@ -117,7 +144,12 @@ mod tests {
     let day = Day19 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day19/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day19/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -126,7 +158,12 @@ mod tests {
     let day = Day19 {};
     assert_eq!(
       "712058625427487",
-      day.solve_part2(&read_file("input/day19/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day19/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -1,8 +1,18 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::parse;
 use crate::AOCDay;
 pub struct Day2 {}
 
-fn string_to_ints(s: &str) -> Vec<i32> {
-  s.split(' ').map(|t| t.parse::<i32>().unwrap()).collect()
+pub const SOLUTION: Solution = Solution::new(2024, 2, &Day2 {});
+
+fn parse_input(input: &[String]) -> Result<Vec<Vec<i32>>, SolveError> {
+  input
+    .iter()
+    .enumerate()
+    .map(|(line_idx, line)| parse::list(line_idx, line, " "))
+    .collect()
 }
 
 fn is_small_gaps(v: &[i32]) -> bool {
@@ -52,34 +62,32 @@ fn does_pass_part_2(v: &Vec<i32>) -> bool {
 }
 
 impl AOCDay for Day2 {
+  type Input = Vec<Vec<i32>>;
+
   fn name(&self) -> String {
     "day2".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    "2".to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some("2".to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some("4".to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    "4".to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let decoded = input
-      .iter()
-      .map(|s| string_to_ints(s))
-      .collect::<Vec<Vec<i32>>>();
+  fn solve_part1(&self, decoded: &Self::Input, _params: &Params) -> Answer {
     let passing_count = decoded.iter().filter(|v| does_pass(v)).count();
-    passing_count.to_string()
+    Ok(passing_count.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let decoded = input
-      .iter()
-      .map(|s| string_to_ints(s))
-      .collect::<Vec<Vec<i32>>>();
+  fn solve_part2(&self, decoded: &Self::Input, _params: &Params) -> Answer {
     let passing_count = decoded.iter().filter(|v| does_pass_part_2(v)).count();
-    passing_count.to_string()
+    Ok(passing_count.to_string())
   }
 }
 
@@ -89,9 +97,9 @@ mod tests {
   use crate::utils::read_file;
 
   #[test]
-  fn test_string_to_ints() {
-    let result = string_to_ints("7 6 4 2 1");
-    assert_eq!(result, vec![7, 6, 4, 2, 1]);
+  fn test_parse_input() {
+    let result = parse_input(&["7 6 4 2 1".to_string()]);
+    assert_eq!(result, Ok(vec![vec![7, 6, 4, 2, 1]]));
   }
 
   #[test]
@@ -115,12 +123,28 @@ mod tests {
   #[test]
   fn test_part_1() {
     let day = Day2 {};
-    assert_eq!("502", day.solve_part1(&read_file("input/day2/part1.txt")));
+    assert_eq!(
+      "502",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day2/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day2 {};
-    assert_eq!("544", day.solve_part2(&read_file("input/day2/part2.txt")));
+    assert_eq!(
+      "544",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day2/part2.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
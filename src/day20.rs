@@ -1,107 +1,36 @@
+use crate::cancel;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Convert, Grid, Point};
 use crate::AOCDay;
 use std::collections::{HashSet, VecDeque};
 
 const PART_1_EXAMPLE: &str = "5";
 const PART_2_EXAMPLE: &str = "285";
 
-type Map = Vec<Vec<char>>;
+/// Only cheats that save at least this many picoseconds count
+const MIN_SAVING: usize = 100;
 
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-struct Point {
-  x: usize,
-  y: usize,
-}
-
-fn get_manhattan_distance(a: Point, b: Point) -> usize {
-  a.x.abs_diff(b.x) + a.y.abs_diff(b.y)
-}
-
-fn parse_input(input: &[String]) -> (Map, Point, Point) {
-  let mut map = input
-    .iter()
-    .map(|line| line.chars().collect())
-    .collect::<Vec<Vec<char>>>();
+fn parse_input(input: &[String]) -> Result<(Grid<char>, Point, Point), SolveError> {
+  let mut map = Grid::parse(input, Some)?;
   let start = map
-    .iter()
-    .enumerate()
-    .find_map(|(y, row)| {
-      row
-        .iter()
-        .enumerate()
-        .find_map(|(x, &c)| if c == 'S' { Some((x, y)) } else { None })
-    })
-    .expect("No start position found");
+    .find(&'S')
+    .ok_or_else(|| SolveError::parse(0, "expected a start, `S`, on the map"))?;
   let end = map
-    .iter()
-    .enumerate()
-    .find_map(|(y, row)| {
-      row
-        .iter()
-        .enumerate()
-        .find_map(|(x, &c)| if c == 'E' { Some((x, y)) } else { None })
-    })
-    .expect("No end position found");
-
-  map[start.1][start.0] = '.';
-  map[end.1][end.0] = '.';
-
-  (
-    map,
-    Point {
-      x: start.0,
-      y: start.1,
-    },
-    Point { x: end.0, y: end.1 },
-  )
-}
+    .find(&'E')
+    .ok_or_else(|| SolveError::parse(0, "expected an end, `E`, on the map"))?;
 
-#[allow(dead_code)]
-fn print_map(map: &Map) {
-  for row in map {
-    println!("{}", row.iter().collect::<String>());
-  }
-  println!("\n----------------\n\n");
-}
+  map[start] = '.';
+  map[end] = '.';
 
-fn is_test(input: &[Vec<char>]) -> bool {
-  input.len() == 15
+  Ok((map, start, end))
 }
 
-fn get_neighbours(map: &Map, location: Point) -> Vec<Point> {
-  let mut neighbours = vec![];
-  if location.x > 0 {
-    neighbours.push(Point {
-      x: location.x - 1,
-      y: location.y,
-    });
-  }
-
-  if location.y > 0 {
-    neighbours.push(Point {
-      x: location.x,
-      y: location.y - 1,
-    });
-  }
-
-  if location.x < map[0].len() - 1 {
-    neighbours.push(Point {
-      x: location.x + 1,
-      y: location.y,
-    });
-  }
-
-  if location.y < map.len() - 1 {
-    neighbours.push(Point {
-      x: location.x,
-      y: location.y + 1,
-    });
-  }
-
-  neighbours
-    .iter()
-    .filter(|point| map[point.y][point.x] == '.')
-    .copied()
-    .collect()
+fn get_neighbours(map: &Grid<char>, location: Point) -> impl Iterator<Item = Point> + '_ {
+  map
+    .neighbours(location)
+    .filter(|&neighbour| map[neighbour] == '.')
 }
 
 fn generate_cheat_paths_for_tile(
@@ -115,7 +44,7 @@ fn generate_cheat_paths_for_tile(
     // get the manhatten distance from the cheat start to the end
     // and filter the ones that are within the path length
     .filter_map(|cheat_end| {
-      let distance = get_manhattan_distance(cheat_start, *cheat_end);
+      let distance = cheat_start.manhattan_distance(*cheat_end).convert().ok()?;
       if distance <= path_length {
         Some((cheat_start, *cheat_end, distance))
       } else {
@@ -125,124 +54,153 @@ fn generate_cheat_paths_for_tile(
     .collect::<HashSet<(Point, Point, usize)>>()
 }
 
-fn get_track_tiles(map: &Map) -> Vec<Point> {
+fn get_track_tiles(map: &Grid<char>) -> Vec<Point> {
   map
     .iter()
-    .enumerate()
-    .flat_map(|(row_idx, col)| {
-      col
-        .iter()
-        .enumerate()
-        .filter(|(_, value)| **value == '.')
-        .map(move |(col_idx, _)| Point {
-          x: col_idx,
-          y: row_idx,
-        })
-    })
+    .filter(|(_, &value)| value == '.')
+    .map(|(point, _)| point)
     .collect::<Vec<Point>>()
 }
 
 fn count_cheat_paths(
-  map: &Map,
+  map: &Grid<char>,
   start: Point,
   end: Point,
   path_length: usize,
   required_saving: usize,
-) -> usize {
+) -> Result<usize, SolveError> {
   let all_track_tiles = get_track_tiles(map);
-  let width = map.len();
-  let height = map[0].len();
-
-  let mut from_start = vec![vec![usize::MAX; width]; height];
+  let mut from_start = Grid::new(map.width(), map.height(), usize::MAX);
   let mut from_start_queue: VecDeque<(Point, usize)> = VecDeque::new();
   from_start_queue.push_back((start, 0));
-  from_start[start.y][start.x] = 0;
+  from_start[start] = 0;
 
   while !from_start_queue.is_empty() {
     let (current, cost) = from_start_queue.pop_front().unwrap();
 
     for neighbour in get_neighbours(map, current) {
       let new_cost = cost + 1;
-      if new_cost < from_start[neighbour.y][neighbour.x] {
-        from_start[neighbour.y][neighbour.x] = new_cost;
+      if new_cost < from_start[neighbour] {
+        from_start[neighbour] = new_cost;
         from_start_queue.push_back((neighbour, new_cost));
       }
     }
   }
 
-  let total_cost = from_start[end.y][end.x];
-
-  let all_cheat_paths = all_track_tiles
-    .iter()
-    .flat_map(|point| generate_cheat_paths_for_tile(&all_track_tiles, *point, path_length))
-    .filter_map(|(cheat_start, cheat_end, cheat_distance)| {
-      let start_cost = from_start[cheat_start.y][cheat_start.x];
-      let end_cost = total_cost - from_start[cheat_end.y][cheat_end.x];
-      let cost = start_cost + cheat_distance + end_cost;
-      let saving = total_cost.saturating_sub(cost);
-
-      match saving {
-        _ if saving >= required_saving => Some((cheat_start, cheat_end, saving)),
-        _ => None,
-      }
-    })
-    .collect::<HashSet<(Point, Point, usize)>>();
+  let total_cost = from_start[end];
+
+  let cancel = cancel::current();
+  let mut all_cheat_paths = HashSet::new();
+  for point in &all_track_tiles {
+    cancel.check()?;
+    let cheat_paths = generate_cheat_paths_for_tile(&all_track_tiles, *point, path_length)
+      .into_iter()
+      .filter_map(|(cheat_start, cheat_end, cheat_distance)| {
+        let start_cost = from_start[cheat_start];
+        let end_cost = total_cost - from_start[cheat_end];
+        let cost = start_cost + cheat_distance + end_cost;
+        let saving = total_cost.saturating_sub(cost);
+
+        match saving {
+          _ if saving >= required_saving => Some((cheat_start, cheat_end, saving)),
+          _ => None,
+        }
+      });
+    all_cheat_paths.extend(cheat_paths);
+  }
 
-  all_cheat_paths.len()
+  Ok(all_cheat_paths.len())
 }
 
 pub struct Day20 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 20, &Day20 {});
+
 impl AOCDay for Day20 {
+  type Input = (Grid<char>, Point, Point);
+
   fn name(&self) -> String {
     "day20".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn params(&self) -> &'static [&'static str] {
+    &["min_saving"]
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (map, start, end) = parse_input(input);
-    let required_saving = if is_test(&map) { 20 } else { 100 };
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, params: &Params) -> Answer {
+    let &(ref map, start, end) = input;
+    let required_saving = params.get_or("min_saving", MIN_SAVING)?;
 
-    let result = count_cheat_paths(&map, start, end, 2, required_saving);
+    let result = count_cheat_paths(map, start, end, 2, required_saving)?;
 
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (map, start, end) = parse_input(input);
-    let required_saving = if is_test(&map) { 50 } else { 100 };
+  fn solve_part2(&self, input: &Self::Input, params: &Params) -> Answer {
+    let &(ref map, start, end) = input;
+    let required_saving = params.get_or("min_saving", MIN_SAVING)?;
 
-    let result = count_cheat_paths(&map, start, end, 20, required_saving);
+    let result = count_cheat_paths(map, start, end, 20, required_saving)?;
 
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::utils::read_file;
+  use crate::utils::{lines, read_file};
+
+  #[test]
+  fn test_parse_errors() {
+    assert_eq!(
+      parse_input(&lines("#.E#")).err(),
+      Some(SolveError::parse(0, "expected a start, `S`, on the map"))
+    );
+    assert_eq!(
+      parse_input(&lines("#S.#")).err(),
+      Some(SolveError::parse(0, "expected an end, `E`, on the map"))
+    );
+  }
 
   #[test]
   fn test_part_1_example() {
     let day = Day20 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day20/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day20/test1.txt")).unwrap(),
+          &Params::from([("min_saving", "20")])
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day20 {};
-    assert_eq!("1321", day.solve_part1(&read_file("input/day20/part1.txt")));
+    assert_eq!(
+      "1321",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day20/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
@@ -250,7 +208,12 @@ mod tests {
     let day = Day20 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day20/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day20/test1.txt")).unwrap(),
+          &Params::from([("min_saving", "50")])
+        )
+        .unwrap()
     );
   }
 
@@ -259,7 +222,12 @@ mod tests {
     let day = Day20 {};
     assert_eq!(
       "971737",
-      day.solve_part2(&read_file("input/day20/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day20/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
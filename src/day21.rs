@@ -1,11 +1,13 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::Convert;
 use crate::AOCDay;
-use itertools::Itertools;
 use memoize::memoize;
 use std::cmp::Ordering;
 use std::string::String;
 
 const PART_1_EXAMPLE: &str = "126384";
-const PART_2_EXAMPLE: &str = "SKIP";
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
 struct Position {
@@ -19,10 +21,29 @@ struct Command {
   steps: usize,
 }
 
-fn parse_input(input: &[String]) -> Vec<Vec<char>> {
+fn parse_input(input: &[String]) -> Result<Vec<Vec<char>>, SolveError> {
   input
     .iter()
-    .map(|line| line.parse::<String>().unwrap().chars().collect())
+    .enumerate()
+    .map(|(line_idx, line)| {
+      if let Some(col_idx) = line.chars().position(|c| !c.is_ascii_digit() && c != 'A') {
+        return Err(
+          SolveError::parse(line_idx, "expected a numeric keypad code").at_column(col_idx),
+        );
+      }
+
+      let digits = line
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+      if !digits.is_empty() && digits.parse::<usize>().is_err() {
+        return Err(SolveError::parse(
+          line_idx,
+          "the code's number is too large",
+        ));
+      }
+      Ok(line.chars().collect())
+    })
     .collect()
 }
 
@@ -52,7 +73,7 @@ fn pos_for_numeric_keypad_char(c: char) -> Position {
     '7' => Position { x: 0, y: 3 },
     '8' => Position { x: 1, y: 3 },
     '9' => Position { x: 2, y: 3 },
-    _ => panic!("Invalid numeric keypad char: {c}"),
+    _ => unreachable!("codes are checked by parse_input, found {c}"),
   }
 }
 
@@ -145,7 +166,7 @@ fn pos_for_directional_keypad_char(c: char) -> Position {
     '>' => Position { x: 2, y: 0 },
     '^' => Position { x: 1, y: 1 },
     'A' => Position { x: 2, y: 1 },
-    _ => panic!("Invalid directional keypad char: {c}"),
+    _ => unreachable!("only moves and A are typed on a directional keypad, found {c}"),
   }
 }
 
@@ -276,43 +297,44 @@ fn generate_shortest_path_len_for(code: Vec<char>, is_part_2: bool) -> usize {
 fn path_from_commands(commands: Vec<Command>) -> Vec<char> {
   commands
     .iter()
-    .flat_map(|c| {
-      std::iter::repeat(c.direction)
-        .take(c.steps)
-        .collect::<Vec<char>>()
-    })
+    .flat_map(|c| std::iter::repeat_n(c.direction, c.steps).collect::<Vec<char>>())
     .collect::<Vec<char>>()
 }
 
 fn calculate_complexity(path_len: usize, code: &[char]) -> usize {
+  // parse_input checks the number fits, and a code without one is worth nothing
   let code = code
     .iter()
-    .skip_while(|c| **c == '0')
-    .filter(|c| c.is_numeric())
-    .collect::<String>()
-    .parse::<usize>()
-    .unwrap();
+    .filter_map(|c| c.to_digit(10))
+    .fold(0, |code, digit| code * 10 + digit.convert_x::<usize>());
 
   path_len * code
 }
 
 pub struct Day21 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 21, &Day21 {});
+
 impl AOCDay for Day21 {
+  type Input = Vec<Vec<char>>;
+
   fn name(&self) -> String {
     "day21".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    None
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let codes = parse_input(input);
+  fn solve_part1(&self, codes: &Self::Input, _params: &Params) -> Answer {
     let values = codes
       .iter()
       .map(|code| (code, generate_shortest_path_len_for(code.clone(), false)))
@@ -320,17 +342,10 @@ impl AOCDay for Day21 {
       .collect::<Vec<usize>>();
 
     let result = values.iter().sum::<usize>();
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let codes = parse_input(input);
-
-    // No test to run
-    if codes[0].iter().join("") == "029A" {
-      return "SKIP".to_string();
-    }
-
+  fn solve_part2(&self, codes: &Self::Input, _params: &Params) -> Answer {
     let values = codes
       .iter()
       .map(|code| (code, generate_shortest_path_len_for(code.clone(), true)))
@@ -338,7 +353,7 @@ impl AOCDay for Day21 {
       .collect::<Vec<usize>>();
 
     let result = values.iter().sum::<usize>();
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
@@ -355,6 +370,15 @@ mod tests {
     assert_eq!(calculate_complexity(sequence.len(), &code), 1972);
   }
 
+  #[test]
+  fn test_codes_without_a_number() {
+    assert_eq!(calculate_complexity(10, &['0', '0', 'A']), 0);
+    assert_eq!(
+      parse_input(&["99999999999999999999A".to_string()]),
+      Err(SolveError::parse(0, "the code's number is too large"))
+    );
+  }
+
   #[test]
   fn test_calculate_complexity_full() {
     let code = "029A".chars().collect::<Vec<char>>();
@@ -443,7 +467,12 @@ mod tests {
     let day = Day21 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day21/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day21/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -452,7 +481,12 @@ mod tests {
     let day = Day21 {};
     assert_eq!(
       "188398",
-      day.solve_part1(&read_file("input/day21/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day21/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -461,7 +495,12 @@ mod tests {
     let day = Day21 {};
     assert_eq!(
       "230049027535970",
-      day.solve_part2(&read_file("input/day21/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day21/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -1,12 +1,24 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use crate::cancel;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "37327623";
 const PART_2_EXAMPLE: &str = "23";
 
-fn parse_input(input: &[String]) -> Vec<i64> {
-  input.iter().map(|line| line.parse().unwrap()).collect()
+fn parse_input(input: &[String]) -> Result<Vec<i64>, SolveError> {
+  input
+    .iter()
+    .enumerate()
+    .map(|(line_idx, line)| {
+      line
+        .parse()
+        .map_err(|msg| SolveError::parse(line_idx, format!("failed to parse secret number: {msg}")))
+    })
+    .collect()
 }
 
 fn mix_number(first: i64, second: i64) -> i64 {
@@ -73,33 +85,43 @@ fn extract_prices(
 
 pub struct Day22 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 22, &Day22 {});
+
 impl AOCDay for Day22 {
+  type Input = Vec<i64>;
+
   fn name(&self) -> String {
     "day22".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let mut secret_numbers = parse_input(input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let cancel = cancel::current();
+    let mut secret_numbers = input.clone();
     for _ in 0..2000 {
+      cancel.check()?;
       secret_numbers = secret_numbers
         .iter()
         .map(|secret_number| evolve_number(*secret_number))
         .collect();
     }
 
-    secret_numbers.iter().sum::<i64>().to_string()
+    Ok(secret_numbers.iter().sum::<i64>().to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let mut secret_numbers = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let mut secret_numbers = input.clone();
     let mut sequences: Vec<VecDeque<(i64, i64)>> =
       vec![vec![(0_i64, 0_i64); 4].into(); secret_numbers.len()];
     let mut sequence_to_price: Vec<HashMap<String, i64>> =
@@ -110,7 +132,9 @@ impl AOCDay for Day22 {
     }
     extract_prices(&sequences, &mut sequence_to_price);
 
+    let cancel = cancel::current();
     for _ in 4..2000 {
+      cancel.check()?;
       step_sequences(&mut secret_numbers, &mut sequences);
       extract_prices(&sequences, &mut sequence_to_price);
     }
@@ -128,9 +152,10 @@ impl AOCDay for Day22 {
           .fold(0, |acc, map| acc + map.get(sequence).unwrap_or(&0))
       })
       .max()
-      .expect("Impossible for sequence_to_price to be empty");
+      // Every buyer sees plenty of sequences, so there are none only without any buyers
+      .ok_or_else(|| SolveError::NoSolution("there are no buyers".to_string()))?;
 
-    banana_count.to_string()
+    Ok(banana_count.to_string())
   }
 }
 
@@ -167,12 +192,25 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_no_buyers() {
+    assert!(matches!(
+      Day22 {}.solve_part2(&vec![], &Params::default()),
+      Err(SolveError::NoSolution(_))
+    ));
+  }
+
   #[test]
   fn test_part_1_example() {
     let day = Day22 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day22/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day22/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -181,7 +219,12 @@ mod tests {
     let day = Day22 {};
     assert_eq!(
       "20332089158",
-      day.solve_part1(&read_file("input/day22/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day22/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -203,13 +246,26 @@ mod tests {
     let day = Day22 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day22/test2.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day22/test2.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day22 {};
-    assert_eq!("2191", day.solve_part2(&read_file("input/day22/part1.txt")));
+    assert_eq!(
+      "2191",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day22/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
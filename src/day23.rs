@@ -2,18 +2,21 @@ use petgraph::{graph::NodeIndex, graph::UnGraph, Graph};
 use regex::Regex;
 use std::collections::HashSet;
 
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "7";
 const PART_2_EXAMPLE: &str = "co,de,ka,ta";
 
-fn parse_input(input: &[String]) -> Graph<&str, (), petgraph::Undirected> {
+fn parse_input(input: &[String]) -> Result<Graph<String, (), petgraph::Undirected>, SolveError> {
   let regex = Regex::new(r"^([a-z]{2})-([a-z]{2})$").unwrap();
-  let mut computers = UnGraph::<&str, ()>::with_capacity(input.len() * 2, 4);
+  let mut computers = UnGraph::<String, ()>::with_capacity(input.len() * 2, 4);
 
-  for line in input {
+  for (line_idx, line) in input.iter().enumerate() {
     let Some(captures) = regex.captures(line) else {
-      panic!("Failed to parse line");
+      return Err(SolveError::parse(line_idx, "expected a connection `ab-cd`"));
     };
     let first_name = captures.get(1).expect("Failed to parse first").as_str();
     let second_name = captures.get(2).expect("Failed to parse second").as_str();
@@ -23,7 +26,7 @@ fn parse_input(input: &[String]) -> Graph<&str, (), petgraph::Undirected> {
       .find(|i| computers[*i] == first_name)
     {
       Some(i) => i,
-      None => computers.add_node(first_name),
+      None => computers.add_node(first_name.to_string()),
     };
 
     let second = match computers
@@ -31,17 +34,20 @@ fn parse_input(input: &[String]) -> Graph<&str, (), petgraph::Undirected> {
       .find(|i| computers[*i] == second_name)
     {
       Some(i) => i,
-      None => computers.add_node(second_name),
+      None => computers.add_node(second_name.to_string()),
     };
 
     computers.add_edge(first, second, ());
   }
 
-  computers
+  Ok(computers)
 }
 
-fn group_key(group: &[NodeIndex], graph: &Graph<&str, (), petgraph::Undirected>) -> String {
-  let mut keys: Vec<&str> = group.iter().map(|i| graph[*i]).collect::<Vec<&str>>();
+fn group_key(group: &[NodeIndex], graph: &Graph<String, (), petgraph::Undirected>) -> String {
+  let mut keys: Vec<&str> = group
+    .iter()
+    .map(|i| graph[*i].as_str())
+    .collect::<Vec<&str>>();
   keys.sort_unstable();
   keys.join(",")
 }
@@ -51,7 +57,7 @@ fn intersect<'a>(a: &'a [NodeIndex], b: &'a [NodeIndex]) -> Vec<NodeIndex> {
 }
 
 fn bron_kerbosch(
-  graph: &Graph<&str, (), petgraph::Undirected>,
+  graph: &Graph<String, (), petgraph::Undirected>,
   largest_subgraph_key: String,
   current: &[NodeIndex],
   candidates: &mut Vec<NodeIndex>,
@@ -98,21 +104,28 @@ fn bron_kerbosch(
 
 pub struct Day23 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 23, &Day23 {});
+
 impl AOCDay for Day23 {
+  type Input = Graph<String, (), petgraph::Undirected>;
+
   fn name(&self) -> String {
     "day23".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let computers = parse_input(input);
+  fn solve_part1(&self, computers: &Self::Input, _params: &Params) -> Answer {
     let mut triplets = HashSet::new();
 
     computers.node_indices().for_each(|node| {
@@ -121,7 +134,7 @@ impl AOCDay for Day23 {
         let second_neighbours = computers.neighbors(neighbour).collect::<Vec<_>>();
         for second_neighbour in second_neighbours {
           if second_neighbour != node && computers.contains_edge(node, second_neighbour) {
-            triplets.insert(group_key(&[node, neighbour, second_neighbour], &computers));
+            triplets.insert(group_key(&[node, neighbour, second_neighbour], computers));
           }
         }
       }
@@ -133,13 +146,18 @@ impl AOCDay for Day23 {
       .filter(|triplet| contains_t.is_match(triplet))
       .collect::<Vec<&String>>();
 
-    with_t.len().to_string()
+    Ok(with_t.len().to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let computers = parse_input(input);
+  fn solve_part2(&self, computers: &Self::Input, _params: &Params) -> Answer {
     let mut all = computers.node_indices().collect::<Vec<NodeIndex>>();
-    bron_kerbosch(&computers, String::new(), &[], &mut all, &mut vec![])
+    Ok(bron_kerbosch(
+      computers,
+      String::new(),
+      &[],
+      &mut all,
+      &mut vec![],
+    ))
   }
 }
 
@@ -153,14 +171,27 @@ mod tests {
     let day = Day23 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day23/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day23/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day23 {};
-    assert_eq!("1512", day.solve_part1(&read_file("input/day23/part1.txt")));
+    assert_eq!(
+      "1512",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day23/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
@@ -168,7 +199,12 @@ mod tests {
     let day = Day23 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day23/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day23/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -177,7 +213,12 @@ mod tests {
     let day = Day23 {};
     assert_eq!(
       "ac,ed,fh,kd,lf,mb,om,pe,qt,uo,uy,vr,wg",
-      day.solve_part2(&read_file("input/day23/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day23/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
@@ -3,10 +3,13 @@ use regex::Regex;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::string::ToString;
 
+use crate::cancel;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "2024";
-const PART_2_EXAMPLE: &str = "SKIP";
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 enum GateType {
@@ -17,19 +20,19 @@ enum GateType {
 }
 
 impl GateType {
-  fn from_str(s: &str) -> Self {
+  fn from_str(s: &str) -> Option<Self> {
     match s {
-      "AND" => GateType::And,
-      "OR" => GateType::Or,
-      "XOR" => GateType::Xor,
-      _ => panic!("Invalid gate type"),
+      "AND" => Some(GateType::And),
+      "OR" => Some(GateType::Or),
+      "XOR" => Some(GateType::Xor),
+      _ => None,
     }
   }
 }
 
 #[allow(clippy::struct_field_names)]
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
-struct Gate {
+pub struct Gate {
   name: String,
   gate_type: GateType,
   input_1: Option<String>,
@@ -37,124 +40,204 @@ struct Gate {
   output: Option<bool>,
 }
 
-fn get_or_create_gate<'a>(
-  name: &str,
-  gate_type: GateType,
-  gates: &'a mut HashMap<String, Gate>,
-) -> &'a mut Gate {
-  let gate = gates.entry(name.to_string()).or_insert_with(|| Gate {
-    name: name.to_string(),
-    gate_type,
-    input_1: None,
-    input_2: None,
-    output: None,
-  });
-
-  gate
+impl Gate {
+  fn inputs(&self) -> impl Iterator<Item = &str> {
+    [&self.input_1, &self.input_2]
+      .into_iter()
+      .flatten()
+      .map(String::as_str)
+  }
 }
 
-fn parse_input(input: &[String]) -> (HashMap<String, Gate>, VecDeque<String>) {
+fn parse_input(input: &[String]) -> Result<(HashMap<String, Gate>, Vec<String>), SolveError> {
   let mut gates = HashMap::<String, Gate>::new();
-  let mut queue = VecDeque::<String>::new();
+  // The line each wire is set on, for errors found after the line has been read
+  let mut wire_lines = HashMap::<String, usize>::new();
 
-  let empty_line_idx = input
-    .iter()
-    .enumerate()
-    .find(|(_, line)| line.is_empty())
-    .expect("Expected an empty line splitting the two parts of the input")
-    .0;
+  let Some(empty_line_idx) = input.iter().position(String::is_empty) else {
+    return Err(SolveError::parse(
+      input.len(),
+      "expected a blank line between the inputs and the gates",
+    ));
+  };
 
   let (inputs, edges_) = input.split_at(empty_line_idx);
   let edges = edges_.split_first().unwrap().1;
 
-  let input_regex = Regex::new(r"^([a-z,0-9]{3}): (\d)$").expect("Invalid input regex");
-  for input in inputs {
+  let input_regex = Regex::new(r"^([a-z,0-9]{3}): ([01])$").expect("Invalid input regex");
+  for (line_idx, input) in inputs.iter().enumerate() {
     let Some(captures) = input_regex.captures(input) else {
-      panic!("Failed to parse input");
+      return Err(SolveError::parse(
+        line_idx,
+        "expected an input wire `x00: 1`",
+      ));
     };
     let gate_name = captures.get(1).expect("Failed to parse name").as_str();
-    let gate_output = captures
-      .get(2)
-      .expect("Failed to parse output")
-      .as_str()
-      .parse::<usize>()
-      .expect("Failed to parse output to integer")
-      == 1;
-
-    gates.entry(gate_name.to_string()).or_insert(Gate {
-      name: gate_name.to_string(),
-      gate_type: GateType::Input,
-      input_1: None,
-      input_2: None,
-      output: Some(gate_output),
-    });
+    let gate_output = &captures[2] == "1";
+
+    if wire_lines.insert(gate_name.to_string(), line_idx).is_some() {
+      return Err(SolveError::parse(
+        line_idx,
+        format!("`{gate_name}` is already set"),
+      ));
+    }
+    gates.insert(
+      gate_name.to_string(),
+      Gate {
+        name: gate_name.to_string(),
+        gate_type: GateType::Input,
+        input_1: None,
+        input_2: None,
+        output: Some(gate_output),
+      },
+    );
   }
 
   let edge_regex = Regex::new(r"^([a-z,0-9]{3}) (AND|OR|XOR) ([a-z,0-9]{3}) -> ([a-z,0-9]{3})$")
     .expect("Invalid edge regex");
-  for edge in edges {
+  let mut edge_captures = Vec::new();
+  for (edge_idx, edge) in edges.iter().enumerate() {
+    let line_idx = empty_line_idx + 1 + edge_idx;
     let Some(captures) = edge_regex.captures(edge) else {
-      panic!("Failed to parse edge");
+      return Err(SolveError::parse(
+        line_idx,
+        "expected a gate `a AND b -> c`",
+      ));
     };
-    let first = captures
-      .get(1)
-      .expect("Failed to parse first gate")
-      .as_str();
-    let second = captures
-      .get(3)
-      .expect("Failed to parse second gate")
-      .as_str();
-    let third = captures
-      .get(4)
-      .expect("Failed to parse third gate")
-      .as_str();
-
-    let gate_type =
-      GateType::from_str(captures.get(2).expect("Failed to parse gate type").as_str());
-
-    let third_gate = get_or_create_gate(third, gate_type, &mut gates);
-    third_gate.input_1 = Some(first.to_string());
-    third_gate.input_2 = Some(second.to_string());
-    queue.push_back(third.to_string());
+    let third = captures.get(4).expect("Failed to parse third gate");
+    if wire_lines
+      .insert(third.as_str().to_string(), line_idx)
+      .is_some()
+    {
+      return Err(
+        SolveError::parse(line_idx, format!("`{}` is already set", third.as_str()))
+          .at_column(third.start()),
+      );
+    }
+    edge_captures.push((line_idx, captures));
   }
 
-  (gates, queue)
+  for (line_idx, captures) in edge_captures {
+    for wire in [captures.get(1), captures.get(3)].into_iter().flatten() {
+      if !wire_lines.contains_key(wire.as_str()) {
+        return Err(
+          SolveError::parse(
+            line_idx,
+            format!("`{}` isn't an input or a gate's output", wire.as_str()),
+          )
+          .at_column(wire.start()),
+        );
+      }
+    }
+
+    let Some(gate_type) = GateType::from_str(&captures[2]) else {
+      return Err(SolveError::parse(line_idx, "expected AND, OR or XOR").at_column(4));
+    };
+    gates.insert(
+      captures[4].to_string(),
+      Gate {
+        name: captures[4].to_string(),
+        gate_type,
+        input_1: Some(captures[1].to_string()),
+        input_2: Some(captures[3].to_string()),
+        output: None,
+      },
+    );
+  }
+
+  let order = evaluation_order(&gates).map_err(|wire| {
+    SolveError::parse(
+      wire_lines.get(&wire).copied().unwrap_or_default(),
+      format!("`{wire}` depends on its own output"),
+    )
+  })?;
+
+  Ok((gates, order))
 }
 
-fn run_device(mut gates: HashMap<String, Gate>, mut queue: VecDeque<String>) -> (u64, u64, u64) {
-  while !queue.is_empty() {
-    let immut_gates = gates.clone();
-    if let Some(cur_gate_name) = queue.pop_front() {
-      let cur_gate = gates.get_mut(&cur_gate_name).expect("Failed to find gate");
+/// The gates in an order where each comes after the gates it takes its inputs from, or a wire on a
+/// loop if there's no such order.
+fn evaluation_order(gates: &HashMap<String, Gate>) -> Result<Vec<String>, String> {
+  let mut waiting_on = HashMap::<&str, usize>::new();
+  let mut readers = HashMap::<&str, Vec<&str>>::new();
+  for (name, gate) in gates {
+    waiting_on.insert(name, gate.inputs().count());
+    for input in gate.inputs() {
+      readers.entry(input).or_default().push(name);
+    }
+  }
 
-      if let (Some(input_1_name), Some(input_2_name)) =
-        (cur_gate.input_1.clone(), cur_gate.input_2.clone())
-      {
-        let input_1 = immut_gates
-          .get(&input_1_name)
-          .expect("Failed to find input 1");
-        let input_2 = immut_gates
-          .get(&input_2_name)
-          .expect("Failed to find input 2");
-
-        if let (Some(output_1), Some(output_2)) = (input_1.output, input_2.output) {
-          let output = match cur_gate.gate_type {
-            GateType::And => output_1 & output_2,
-            GateType::Or => output_1 | output_2,
-            GateType::Xor => output_1 ^ output_2,
-            GateType::Input => panic!("Cannot have input gate in queue"),
-          };
-          cur_gate.output = Some(output);
-        } else {
-          // Inputs not ready yet, push back to the end of the queue
-          queue.push_back(cur_gate_name);
+  let mut ready = waiting_on
+    .iter()
+    .filter(|(_, &count)| count == 0)
+    .map(|(&name, _)| name)
+    .collect::<VecDeque<&str>>();
+  let mut order = Vec::new();
+  while let Some(name) = ready.pop_front() {
+    order.push(name.to_string());
+    for &reader in readers.get(name).into_iter().flatten() {
+      if let Some(count) = waiting_on.get_mut(reader) {
+        *count -= 1;
+        if *count == 0 {
+          ready.push_back(reader);
         }
-      } else {
-        panic!("Invalid gate - all non-INPUT gates should have inputs");
       }
     }
   }
 
+  // Each gate left is waiting on another one that's left, so following them goes round a loop
+  let is_waiting = |wire: &str| waiting_on.get(wire).is_some_and(|&count| count > 0);
+  let Some(mut wire) = waiting_on
+    .keys()
+    .copied()
+    .filter(|&wire| is_waiting(wire))
+    .min()
+  else {
+    return Ok(order);
+  };
+  let mut seen = HashSet::new();
+  while seen.insert(wire) {
+    let Some(next) = gates
+      .get(wire)
+      .and_then(|gate| gate.inputs().find(|&input| is_waiting(input)))
+    else {
+      break;
+    };
+    wire = next;
+  }
+  Err(wire.to_string())
+}
+
+/// Sets every gate's output, going through them in `order`.
+fn run_device(
+  mut gates: HashMap<String, Gate>,
+  order: &[String],
+) -> Result<(u64, u64, u64), SolveError> {
+  let cancel = cancel::current();
+  for gate_name in order {
+    cancel.check()?;
+    let Some(gate) = gates.get(gate_name) else {
+      continue;
+    };
+    let [input_1, input_2] = [&gate.input_1, &gate.input_2].map(|wire| {
+      wire
+        .as_ref()
+        .and_then(|wire| gates.get(wire))
+        .and_then(|input| input.output)
+        == Some(true)
+    });
+    let output = match gate.gate_type {
+      GateType::And => input_1 & input_2,
+      GateType::Or => input_1 | input_2,
+      GateType::Xor => input_1 ^ input_2,
+      // Already set by the input
+      GateType::Input => continue,
+    };
+    if let Some(gate) = gates.get_mut(gate_name) {
+      gate.output = Some(output);
+    }
+  }
+
   let mut x_result = 0_u64;
   let xxx_regex = Regex::new(r"^x[0-9]{2}$").expect("Failed to create XXX regex");
   gates
@@ -162,7 +245,7 @@ fn run_device(mut gates: HashMap<String, Gate>, mut queue: VecDeque<String>) ->
     .filter(|(gate_name, _)| xxx_regex.is_match(gate_name))
     .sorted_by(|(a, _), (b, _)| b.cmp(a))
     .for_each(|(_, gate)| {
-      x_result = x_result * 2 + u64::from(gate.output.unwrap());
+      x_result = x_result * 2 + u64::from(gate.output == Some(true));
     });
 
   let mut y_result = 0_u64;
@@ -172,7 +255,7 @@ fn run_device(mut gates: HashMap<String, Gate>, mut queue: VecDeque<String>) ->
     .filter(|(gate_name, _)| yxx_regex.is_match(gate_name))
     .sorted_by(|(a, _), (b, _)| b.cmp(a))
     .for_each(|(_, gate)| {
-      y_result = y_result * 2 + u64::from(gate.output.unwrap());
+      y_result = y_result * 2 + u64::from(gate.output == Some(true));
     });
 
   let mut z_result = 0_u64;
@@ -182,9 +265,26 @@ fn run_device(mut gates: HashMap<String, Gate>, mut queue: VecDeque<String>) ->
     .filter(|(gate_name, _)| zxx_regex.is_match(gate_name))
     .sorted_by(|(a, _), (b, _)| b.cmp(a))
     .for_each(|(_, gate)| {
-      z_result = z_result * 2 + u64::from(gate.output.unwrap());
+      z_result = z_result * 2 + u64::from(gate.output == Some(true));
     });
-  (x_result, y_result, z_result)
+  Ok((x_result, y_result, z_result))
+}
+
+/// Whether the circuit has the shape of an adder, where each pair of input bits, `xNN` and `yNN`,
+/// feeds both an XOR gate and an AND gate.
+fn is_adder(gates: &HashMap<String, Gate>) -> bool {
+  let adds = |x: &str, y: &str, gate_type: &GateType| {
+    gates.values().any(|gate| {
+      gate.gate_type == *gate_type && gate.inputs().sorted().collect::<Vec<&str>>() == [x, y]
+    })
+  };
+
+  let mut x_wires = gates.keys().filter(|name| name.starts_with('x')).peekable();
+  x_wires.peek().is_some()
+    && x_wires.all(|x| {
+      let y = format!("y{}", &x[1..]);
+      adds(x, &y, &GateType::Xor) && adds(x, &y, &GateType::And)
+    })
 }
 
 fn is_input_wire(wire: Option<String>) -> bool {
@@ -203,32 +303,40 @@ fn are_inputs_first_bit(gate: &Gate) -> bool {
 
 pub struct Day24 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 24, &Day24 {});
+
 impl AOCDay for Day24 {
+  type Input = (HashMap<String, Gate>, Vec<String>);
+
   fn name(&self) -> String {
     "day24".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    None
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (gates, queue) = parse_input(input);
-    let (_, _, z_result) = run_device(gates, queue);
-    z_result.to_string()
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (gates, order) = input;
+    let (_, _, z_result) = run_device(gates.clone(), order)?;
+    Ok(z_result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (gates, _) = parse_input(input);
-    let is_test = input.len() == 19;
-    if is_test {
-      // The test for this part demonstrates an circuit that performs a bitwise AND operation
-      // Whereas the problem wants us to find an adder. So it's not very useful.
-      return "SKIP".to_string();
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (gates, _) = input;
+    // Part 2's example is a circuit that ANDs the inputs, which has no wires to find
+    if !is_adder(gates) {
+      return Err(SolveError::NotApplicable(
+        "the circuit is not an adder".to_string(),
+      ));
     }
 
     let mut faulty_gates = HashSet::new();
@@ -237,10 +345,10 @@ impl AOCDay for Day24 {
       .filter(|gate| gate.name.starts_with('z'))
       .sorted_by(|a, b| b.name.cmp(&a.name))
       .next()
-      .unwrap()
+      .ok_or_else(|| SolveError::NoSolution("the circuit has no z outputs".to_string()))?
       .name;
 
-    for (gate_name, gate) in &gates {
+    for (gate_name, gate) in gates {
       if gate.name.starts_with('z') && gate.name != *final_z_gate_name {
         if gate.gate_type != GateType::Xor {
           faulty_gates.insert(gate_name);
@@ -276,7 +384,7 @@ impl AOCDay for Day24 {
       }
     }
 
-    faulty_gates.iter().sorted().join(",")
+    Ok(faulty_gates.iter().sorted().join(","))
   }
 }
 
@@ -285,12 +393,38 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  #[test]
+  fn test_parse_errors() {
+    let parse = |input: &str| parse_input(&crate::utils::lines(input));
+    assert_eq!(
+      parse("x00: 1\n\nx00 AND qqq -> z00").err(),
+      Some(SolveError::parse(2, "`qqq` isn't an input or a gate's output").at_column(8))
+    );
+    assert_eq!(
+      parse("x00: 1\n\nx00 AND z01 -> z00\nx00 AND z00 -> z01").err(),
+      Some(SolveError::parse(2, "`z00` depends on its own output"))
+    );
+    assert_eq!(
+      parse("x00: 1\nx00: 0\n\nx00 AND x00 -> z00").err(),
+      Some(SolveError::parse(1, "`x00` is already set"))
+    );
+    assert_eq!(
+      parse("x00: 1\n\nx00 AND x00 -> x00").err(),
+      Some(SolveError::parse(2, "`x00` is already set").at_column(15))
+    );
+  }
+
   #[test]
   fn test_part_1_example() {
     let day = Day24 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day24/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day24/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -299,7 +433,26 @@ mod tests {
     let day = Day24 {};
     assert_eq!(
       "55920211035878",
-      day.solve_part1(&read_file("input/day24/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day24/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
+  }
+
+  #[test]
+  fn test_part_2_example() {
+    let day = Day24 {};
+    assert_eq!(
+      day.solve_part2(
+        &day.parse(&read_file("input/day24/test2.txt")).unwrap(),
+        &Params::default()
+      ),
+      Err(SolveError::NotApplicable(
+        "the circuit is not an adder".to_string()
+      ))
     );
   }
 
@@ -308,7 +461,12 @@ mod tests {
     let day = Day24 {};
     assert_eq!(
       "btb,cmv,mwp,rdg,rmj,z17,z23,z30",
-      day.solve_part2(&read_file("input/day24/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day24/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
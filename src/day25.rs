@@ -1,53 +1,64 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::parse;
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "3";
-const PART_2_EXAMPLE: &str = "SKIP";
 
-fn parse_input(input: &[String]) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+/// The height of each column of the locks, then of the keys
+type Schematics = (Vec<Vec<usize>>, Vec<Vec<usize>>);
+
+fn parse_input(input: &[String]) -> Result<Schematics, SolveError> {
   let mut locks = Vec::new();
   let mut keys = Vec::new();
 
-  input.split(String::is_empty).for_each(|schematic| {
-    let grid = schematic
-      .iter()
-      .map(|line| line.chars().collect::<Vec<char>>())
-      .collect::<Vec<Vec<char>>>();
-    let col_lengths = (0..grid[0].len())
-      .map(|col_idx| grid.iter().map(|row| row[col_idx]).collect::<Vec<char>>())
-      .map(|col| col.iter().filter(|c| **c == '#').count() - 1)
+  for (first_line_idx, schematic) in parse::sections(input) {
+    let grid = parse::char_grid(first_line_idx, schematic)?;
+    let col_lengths = grid
+      .columns()
+      .map(|col| col.filter(|c| **c == '#').count() - 1)
       .collect::<Vec<usize>>();
 
-    let is_lock = grid[0].iter().filter(|c| **c == '.').count() == 0;
+    let is_lock = grid.rows().next().is_some_and(|row| !row.contains(&'.'));
     if is_lock {
       locks.push(col_lengths);
     } else {
       keys.push(col_lengths);
     }
-  });
+  }
 
-  (locks, keys)
+  Ok((locks, keys))
 }
 
 pub struct Day25 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 25, &Day25 {});
+
 impl AOCDay for Day25 {
+  type Input = Schematics;
+
   fn name(&self) -> String {
     "day25".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    None
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (keys, locks) = parse_input(input);
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let (keys, locks) = input;
     let mut result = 0;
     for key in keys {
-      for lock in &locks {
+      for lock in locks {
         if lock
           .iter()
           .enumerate()
@@ -59,11 +70,13 @@ impl AOCDay for Day25 {
         }
       }
     }
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, _input: &[String]) -> String {
-    "SKIP".to_string()
+  fn solve_part2(&self, _input: &Self::Input, _params: &Params) -> Answer {
+    Err(SolveError::NotApplicable(
+      "day 25 only has one part".to_string(),
+    ))
   }
 }
 
@@ -77,13 +90,26 @@ mod tests {
     let day = Day25 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day25/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day25/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day25 {};
-    assert_eq!("2978", day.solve_part1(&read_file("input/day25/part1.txt")));
+    assert_eq!(
+      "2978",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day25/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
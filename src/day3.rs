@@ -2,10 +2,15 @@ use core::panic;
 
 use regex::Regex;
 
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
 use crate::AOCDay;
 
 pub struct Day3 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 3, &Day3 {});
+
 fn parse_line(line: &str) -> i32 {
   let re = Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
   let result: i32 = re
@@ -56,29 +61,37 @@ fn parse_line_p2(line: &str) -> i32 {
 }
 
 impl AOCDay for Day3 {
+  type Input = Vec<String>;
+
   fn name(&self) -> String {
     "day3".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    "161".to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some("161".to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some("48".to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    "48".to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    Ok(input.to_vec())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    input
-      .iter()
-      .map(|line| parse_line(line))
-      .sum::<i32>()
-      .to_string()
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    Ok(
+      input
+        .iter()
+        .map(|line| parse_line(line))
+        .sum::<i32>()
+        .to_string(),
+    )
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
     let input_single_line = input.join("");
-    parse_line_p2(&input_single_line).to_string()
+    Ok(parse_line_p2(&input_single_line).to_string())
   }
 }
 
@@ -108,7 +121,12 @@ mod tests {
     let day = Day3 {};
     assert_eq!(
       "167090022",
-      day.solve_part1(&read_file("input/day3/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day3/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -117,7 +135,12 @@ mod tests {
     let day = Day3 {};
     assert_eq!(
       "89823704",
-      day.solve_part2(&read_file("input/day3/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day3/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
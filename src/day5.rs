@@ -1,11 +1,15 @@
-use std::result::Result;
-
-use regex::Regex;
+use std::collections::HashSet;
 
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::parse;
 use crate::AOCDay;
 
 pub struct Day5 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 5, &Day5 {});
+
 struct Rule {
   page_before: i32,
   page_after: i32,
@@ -13,43 +17,43 @@ struct Rule {
 
 type Update = Vec<i32>;
 
-struct Input {
+pub struct PrintQueue {
   rules: Vec<Rule>,
   updates: Vec<Update>,
 }
 
-fn parse_input(input: &[String]) -> Input {
-  let mut rules = Vec::new();
-  let mut updates = Vec::new();
-
-  let rule_re = Regex::new(r"(\d+)\|(\d+)").unwrap();
-  let update_re = Regex::new(r"(\d+)").unwrap();
-
-  for line in input {
-    if line.is_empty() {
-      continue;
-    }
-
-    if let Some(capture) = rule_re.captures(line) {
-      let page_before = capture.get(1).map(|m| m.as_str().parse::<i32>());
-      let page_after = capture.get(2).map(|m| m.as_str().parse::<i32>());
-      if let (Some(Ok(page_before)), Some(Ok(page_after))) = (page_before, page_after) {
-        rules.push(Rule {
+fn parse_input(input: &[String]) -> Result<PrintQueue, SolveError> {
+  let mut sections = parse::sections(input);
+  let (Some((_, rule_lines)), Some((first_update_idx, update_lines))) =
+    (sections.next(), sections.next())
+  else {
+    return Err(SolveError::parse(
+      input.len(),
+      "expected a blank line between the rules and the updates",
+    ));
+  };
+
+  let rules = rule_lines
+    .iter()
+    .enumerate()
+    .map(
+      |(line_idx, line)| match parse::list(line_idx, line, "|")?[..] {
+        [page_before, page_after] => Ok(Rule {
           page_before,
           page_after,
-        });
-      }
-    } else {
-      let update = update_re
-        .captures_iter(line)
-        .filter_map(|capture| capture.get(1).map(|m| m.as_str().parse::<i32>()))
-        .filter_map(Result::ok)
-        .collect();
-      updates.push(update);
-    }
-  }
+        }),
+        _ => Err(SolveError::parse(line_idx, "expected `before|after`")),
+      },
+    )
+    .collect::<Result<Vec<Rule>, SolveError>>()?;
+
+  let updates = update_lines
+    .iter()
+    .enumerate()
+    .map(|(line_idx, line)| parse::list(first_update_idx + line_idx, line, ","))
+    .collect::<Result<Vec<Update>, SolveError>>()?;
 
-  Input { rules, updates }
+  Ok(PrintQueue { rules, updates })
 }
 
 fn does_update_follow_rule(update: &Update, rule: &Rule) -> bool {
@@ -67,78 +71,82 @@ fn is_update_correct(update: &Update, rules: &[Rule]) -> bool {
     .all(|rule| does_update_follow_rule(update, rule))
 }
 
-fn get_middle_value(update: &Update) -> i32 {
-  match update.get(update.len() / 2) {
-    Some(&middle) => middle,
-    None => panic!("Update is empty. This should be impossible."),
-  }
+fn get_middle_value(update: &Update) -> Result<i32, SolveError> {
+  update
+    .get(update.len() / 2)
+    .copied()
+    .ok_or_else(|| SolveError::NoSolution("an update has no pages".to_string()))
 }
 
-fn fix_update(update: &Update, rules: &[Rule]) -> Update {
+/// Reorders `update` until it follows every rule, by moving the first page of each broken rule to
+/// just after the second. Rules that contradict each other would have it going round in circles.
+fn fix_update(update: &Update, rules: &[Rule]) -> Result<Update, SolveError> {
   let mut fixed_update = update.clone();
-  while !is_update_correct(&fixed_update, rules) {
-    if let Some(next_broken_rule) = rules
-      .iter()
-      .find(|rule| !does_update_follow_rule(&fixed_update, rule))
-    {
-      if let (Some(before_idx), Some(after_idx)) = (
-        fixed_update
-          .iter()
-          .position(|x| *x == next_broken_rule.page_before),
-        fixed_update
-          .iter()
-          .position(|x| *x == next_broken_rule.page_after),
-      ) {
-        let element = fixed_update.remove(before_idx);
-        fixed_update.insert(after_idx, element);
-      } else {
-        panic!("Broken rule not found in update");
-      }
-    } else {
-      panic!("No broken rule found");
+  let mut seen = HashSet::new();
+  while let Some(next_broken_rule) = rules
+    .iter()
+    .find(|rule| !does_update_follow_rule(&fixed_update, rule))
+  {
+    if !seen.insert(fixed_update.clone()) {
+      return Err(SolveError::NoSolution(format!(
+        "the rules contradict each other for update {update:?}"
+      )));
+    }
+
+    // A broken rule has both of its pages in the update
+    let position = |page: i32| fixed_update.iter().position(|x| *x == page);
+    if let (Some(before_idx), Some(after_idx)) = (
+      position(next_broken_rule.page_before),
+      position(next_broken_rule.page_after),
+    ) {
+      let element = fixed_update.remove(before_idx);
+      fixed_update.insert(after_idx, element);
     }
   }
-  fixed_update
+  Ok(fixed_update)
 }
 
 impl AOCDay for Day5 {
+  type Input = PrintQueue;
+
   fn name(&self) -> String {
     "day5".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    "143".to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some("143".to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    "123".to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some("123".to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(input);
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
     let result = input
       .updates
       .iter()
       .filter(|update| is_update_correct(update, &input.rules))
       .map(get_middle_value)
-      .sum::<i32>();
+      .sum::<Result<i32, SolveError>>()?;
 
-    result.to_string()
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let input = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
     let bad_updates = input
       .updates
       .iter()
       .filter(|update| !is_update_correct(update, &input.rules));
 
     let result = bad_updates
-      .map(|update| fix_update(update, &input.rules))
-      .map(|update| get_middle_value(&update))
-      .sum::<i32>();
+      .map(|update| get_middle_value(&fix_update(update, &input.rules)?))
+      .sum::<Result<i32, SolveError>>()?;
 
-    result.to_string()
+    Ok(result.to_string())
   }
 }
 
@@ -147,15 +155,49 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  #[test]
+  fn test_contradictory_rules() {
+    let rules = [
+      Rule {
+        page_before: 1,
+        page_after: 2,
+      },
+      Rule {
+        page_before: 2,
+        page_after: 1,
+      },
+    ];
+    assert!(matches!(
+      fix_update(&vec![1, 2], &rules),
+      Err(SolveError::NoSolution(_))
+    ));
+  }
+
   #[test]
   fn test_part_1() {
     let day = Day5 {};
-    assert_eq!("7365", day.solve_part1(&read_file("input/day5/part1.txt")));
+    assert_eq!(
+      "7365",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day5/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day5 {};
-    assert_eq!("5770", day.solve_part2(&read_file("input/day5/part1.txt")));
+    assert_eq!(
+      "5770",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day5/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
@@ -1,141 +1,69 @@
 use std::collections::HashSet;
 use strum_macros::EnumIter;
 
+use crate::cancel;
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::{Direction, Grid, Point};
 use crate::AOCDay;
 
 pub struct Day6 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 6, &Day6 {});
+
 #[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
-enum Tile {
+pub enum Tile {
   Starting,
   Empty,
   Visited,
   Obstacle,
 }
 
-#[derive(Debug, Clone, Copy, EnumIter, PartialEq, std::cmp::Eq, std::hash::Hash)]
-enum Facing {
-  Up,
-  Down,
-  Left,
-  Right,
-}
-
-impl Facing {
-  fn turn_clockwise(self) -> Facing {
-    match self {
-      Facing::Up => Facing::Right,
-      Facing::Right => Facing::Down,
-      Facing::Down => Facing::Left,
-      Facing::Left => Facing::Up,
-    }
+impl std::fmt::Display for Tile {
+  fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    let char = match self {
+      Tile::Starting => '^',
+      Tile::Empty => '.',
+      Tile::Visited => 'X',
+      Tile::Obstacle => '#',
+    };
+    write!(f, "{char}")
   }
 }
 
-type Map = Vec<Vec<Tile>>;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Location {
-  row: usize,
-  col: usize,
-}
-
-#[allow(dead_code)]
-fn print_map(map: &Map) {
-  for row in map {
-    for tile in row {
-      match tile {
-        Tile::Starting => print!("^"),
-        Tile::Empty => print!("."),
-        Tile::Visited => print!("X"),
-        Tile::Obstacle => print!("#"),
-      }
-    }
-    println!();
+fn tile_from_char(char: char) -> Option<Tile> {
+  match char {
+    '.' => Some(Tile::Empty),
+    '^' => Some(Tile::Starting),
+    '#' => Some(Tile::Obstacle),
+    _ => None,
   }
 }
 
-fn tile_from_string(string: &str) -> Tile {
-  match string {
-    "." => Tile::Empty,
-    "^" => Tile::Starting,
-    "#" => Tile::Obstacle,
-    _ => panic!("Unexpected input"),
-  }
-}
+fn next_location(
+  map: &Grid<Tile>,
+  location: Point,
+  facing: Direction,
+) -> Option<(Point, Direction)> {
+  let new_location = facing.step(location);
 
-fn parse_input(input: &[String]) -> Map {
-  let mut result: Map = Map::new();
-
-  for line in input {
-    if !line.is_empty() {
-      let line_by_character = line
-        .clone()
-        .split("")
-        .map(ToString::to_string)
-        .collect::<Vec<String>>();
-      let trimmed_line_by_character = line_by_character[1..line_by_character.len() - 1].to_vec();
-      result.push(
-        trimmed_line_by_character
-          .into_iter()
-          .map(|char| tile_from_string(&char))
-          .collect::<Vec<Tile>>(),
-      );
-    }
+  match map.get(new_location)? {
+    Tile::Obstacle => Some((location, facing.turn_right())),
+    Tile::Empty | Tile::Visited | Tile::Starting => Some((new_location, facing)),
   }
-
-  result
 }
 
-fn get_tile(map: &Map, row: usize, col: usize) -> &Tile {
-  match map.get(row) {
-    Some(row) => row.get(col).expect("Invalid location"),
-    None => panic!("Invalid location"),
-  }
-}
+fn parse_input(input: &[String]) -> Result<(Grid<Tile>, Point), SolveError> {
+  let map = Grid::parse(input, tile_from_char)?;
+  let starting_location = map
+    .find(&Tile::Starting)
+    .ok_or_else(|| SolveError::parse(0, "expected a guard, `^`, on the map"))?;
 
-fn next_location(map: &Map, location: Location, facing: Facing) -> Option<(Location, Facing)> {
-  let row = location.row;
-  let col = location.col;
-  let max_row = map.len() - 1;
-  let max_col = map[0].len() - 1;
-
-  match (facing, row, col) {
-    (Facing::Up, 0, _) | (Facing::Left, _, 0) => None,
-    (Facing::Down, row, _) if row == max_row => None,
-    (Facing::Right, _, col) if col == max_col => None,
-    _ => {
-      let new_location = match facing {
-        Facing::Up => Location { row: row - 1, col },
-        Facing::Down => Location { row: row + 1, col },
-        Facing::Left => Location { row, col: col - 1 },
-        Facing::Right => Location { row, col: col + 1 },
-      };
-
-      let tile = get_tile(map, new_location.row, new_location.col);
-      match tile {
-        Tile::Obstacle => Some((location, facing.turn_clockwise())),
-        Tile::Empty | Tile::Visited | Tile::Starting => Some((new_location, facing)),
-      }
-    }
-  }
-}
-
-fn find_starting_location(map: &Map) -> Location {
-  for (row_index, row) in map.iter().enumerate() {
-    for (col_index, tile) in row.iter().enumerate() {
-      if *tile == Tile::Starting {
-        return Location {
-          row: row_index,
-          col: col_index,
-        };
-      }
-    }
-  }
-  panic!("No starting location found");
+  Ok((map, starting_location))
 }
 
-fn run_simulation(map: &Map, starting_location: Location, facing: Facing) -> HashSet<Location> {
+fn run_simulation(map: &Grid<Tile>, starting_location: Point, facing: Direction) -> HashSet<Point> {
   let mut current_location = starting_location;
   let mut current_facing = facing;
 
@@ -157,7 +85,7 @@ fn run_simulation(map: &Map, starting_location: Location, facing: Facing) -> Has
   visited.iter().map(|(location, _)| *location).collect()
 }
 
-fn contains_loop(map: &Map, starting_location: Location, facing: Facing) -> bool {
+fn contains_loop(map: &Grid<Tile>, starting_location: Point, facing: Direction) -> bool {
   let mut current_location = starting_location;
   let mut current_facing = facing;
 
@@ -183,59 +111,89 @@ fn contains_loop(map: &Map, starting_location: Location, facing: Facing) -> bool
 }
 
 impl AOCDay for Day6 {
+  type Input = (Grid<Tile>, Point);
+
   fn name(&self) -> String {
     "day6".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    "41".to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some("41".to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    "6".to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some("6".to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let map = parse_input(input);
-    let starting_location = find_starting_location(&map);
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let &(ref map, starting_location) = input;
 
-    let visited = run_simulation(&map, starting_location, Facing::Up);
-    visited.len().to_string()
+    let visited = run_simulation(map, starting_location, Direction::Up);
+    Ok(visited.len().to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let map = parse_input(input);
-    let starting_location = find_starting_location(&map);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let &(ref map, starting_location) = input;
 
-    let visited_ids = run_simulation(&map, starting_location, Facing::Up);
+    let visited_ids = run_simulation(map, starting_location, Direction::Up);
 
-    let count = visited_ids
-      .iter()
-      .filter(|location| {
-        let mut inner_map = map.clone();
-        inner_map[location.row][location.col] = Tile::Obstacle;
-        contains_loop(&inner_map, starting_location, Facing::Up)
-      })
-      .count();
+    let cancel = cancel::current();
+    let mut count = 0;
+    for location in &visited_ids {
+      cancel.check()?;
+      let mut inner_map = map.clone();
+      inner_map[*location] = Tile::Obstacle;
+      if contains_loop(&inner_map, starting_location, Direction::Up) {
+        count += 1;
+      }
+    }
 
-    count.to_string()
+    Ok(count.to_string())
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::utils::read_file;
+  use crate::utils::{lines, read_file};
+
+  #[test]
+  fn test_parse_errors() {
+    assert_eq!(
+      parse_input(&lines("..#\n...")).err(),
+      Some(SolveError::parse(0, "expected a guard, `^`, on the map"))
+    );
+  }
 
   #[test]
   fn test_part_1() {
     let day = Day6 {};
-    assert_eq!("4663", day.solve_part1(&read_file("input/day6/part1.txt")));
+    assert_eq!(
+      "4663",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day6/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day6 {};
-    assert_eq!("1530", day.solve_part2(&read_file("input/day6/part1.txt")));
+    assert_eq!(
+      "1530",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day6/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
@@ -1,26 +1,19 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::parse;
 use crate::AOCDay;
 
-use itertools::Itertools;
-
-fn parse_input(input: &[String]) -> Vec<(u64, Vec<u64>)> {
+fn parse_input(input: &[String]) -> Result<Vec<(u64, Vec<u64>)>, SolveError> {
   input
     .iter()
-    .map(|x| {
-      if let Some((test, values)) = x.split(": ").collect_tuple() {
-        let Some(test) = test.parse::<u64>().ok() else {
-          panic!("Failed to parse test value into u64");
-        };
-        let values = values
-          .split(' ')
-          .map(|x| match x.parse::<u64>() {
-            Ok(num) => num,
-            Err(msg) => panic!("Failed to parse values into Vec<u64>: {msg}"),
-          })
-          .collect();
-        (test, values)
-      } else {
-        panic!("Invalid input");
-      }
+    .enumerate()
+    .map(|(line_idx, line)| {
+      let (test, values) = parse::key_value(line_idx, line)?;
+      Ok((
+        parse::value(line_idx, test)?,
+        parse::list(line_idx, values, " ")?,
+      ))
     })
     .collect()
 }
@@ -60,37 +53,45 @@ fn is_valid_part2(test: u64, values: &[u64], current: u64) -> bool {
 
 pub struct Day7 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 7, &Day7 {});
+
 impl AOCDay for Day7 {
+  type Input = Vec<(u64, Vec<u64>)>;
+
   fn name(&self) -> String {
     "day7".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    "3749".to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some("3749".to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    "11387".to_string()
+  fn test_answer_part2(&self) -> Option<String> {
+    Some("11387".to_string())
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    input
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
+  }
+
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let result = input
       .iter()
       .filter(|row| is_valid(row.0, row.1.split_first().unwrap().1, row.1[0]))
       .map(|x| x.0)
-      .sum::<u64>()
-      .to_string()
+      .sum::<u64>();
+
+    Ok(result.to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    input
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let result = input
       .iter()
       .filter(|row| is_valid_part2(row.0, row.1.split_first().unwrap().1, row.1[0]))
       .map(|x| x.0)
-      .sum::<u64>()
-      .to_string()
+      .sum::<u64>();
+
+    Ok(result.to_string())
   }
 }
 
@@ -104,7 +105,12 @@ mod tests {
     let day = Day7 {};
     assert_eq!(
       "1153997401072",
-      day.solve_part1(&read_file("input/day7/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day7/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -113,7 +119,12 @@ mod tests {
     let day = Day7 {};
     assert_eq!(
       "97902809384118",
-      day.solve_part2(&read_file("input/day7/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day7/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
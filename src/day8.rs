@@ -1,3 +1,7 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::Convert;
 use crate::AOCDay;
 
 use std::collections::HashSet;
@@ -6,7 +10,7 @@ const PART_1_EXAMPLE: &str = "14";
 const PART_2_EXAMPLE: &str = "34";
 
 #[derive(Debug, Copy, Clone)]
-struct Antenna {
+pub struct Antenna {
   row: usize,
   col: usize,
   frequency: char,
@@ -15,8 +19,8 @@ struct Antenna {
 impl Antenna {
   fn get_location_as_position(&self) -> Position {
     Position {
-      row: i64::try_from(self.row).unwrap(),
-      col: i64::try_from(self.col).unwrap(),
+      row: self.row.convert_x(),
+      col: self.col.convert_x(),
     }
   }
 }
@@ -57,10 +61,10 @@ fn is_in_bounds(row_count: usize, col_count: usize, position: Position) -> bool
     && col >= 0
     && usize::try_from(row)
       .ok()
-      .map_or(false, |row_idx| row_idx < row_count)
+      .is_some_and(|row_idx| row_idx < row_count)
     && usize::try_from(col)
       .ok()
-      .map_or(false, |col_idx| col_idx < col_count)
+      .is_some_and(|col_idx| col_idx < col_count)
 }
 
 fn get_antinodes(
@@ -142,21 +146,29 @@ fn get_antinodes_part2(
 
 pub struct Day8 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 8, &Day8 {});
+
 impl AOCDay for Day8 {
+  type Input = (usize, usize, Vec<Antenna>);
+
   fn name(&self) -> String {
     "day8".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    Ok(parse_input(input))
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let (row_count, col_count, antennas) = parse_input(input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let &(row_count, col_count, ref antennas) = input;
 
     let mut antinodes: HashSet<Position> = HashSet::new();
     antennas.iter().enumerate().for_each(|(idx, antenna)| {
@@ -177,11 +189,11 @@ impl AOCDay for Day8 {
         });
     });
 
-    antinodes.len().to_string()
+    Ok(antinodes.len().to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let (row_count, col_count, antennas) = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let &(row_count, col_count, ref antennas) = input;
 
     let mut antinodes: HashSet<Position> = HashSet::new();
     antennas.iter().enumerate().for_each(|(idx, antenna)| {
@@ -202,7 +214,7 @@ impl AOCDay for Day8 {
         });
     });
 
-    antinodes.len().to_string()
+    Ok(antinodes.len().to_string())
   }
 }
 
@@ -216,14 +228,27 @@ mod tests {
     let day = Day8 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day8/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day8/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_1() {
     let day = Day8 {};
-    assert_eq!("359", day.solve_part1(&read_file("input/day8/part1.txt")));
+    assert_eq!(
+      "359",
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day8/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 
   #[test]
@@ -231,13 +256,26 @@ mod tests {
     let day = Day8 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day8/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day8/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
   #[test]
   fn test_part_2() {
     let day = Day8 {};
-    assert_eq!("1293", day.solve_part2(&read_file("input/day8/part1.txt")));
+    assert_eq!(
+      "1293",
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day8/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
+    );
   }
 }
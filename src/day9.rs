@@ -1,3 +1,7 @@
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
+use crate::utils::Convert;
 use crate::AOCDay;
 
 const PART_1_EXAMPLE: &str = "1928";
@@ -11,18 +15,31 @@ enum BlockType {
   FreeSpace,
 }
 
-struct Block {
+#[derive(Clone)]
+pub struct Block {
   id: usize,
   length: usize,
   r#type: BlockType,
   compacted: bool,
 }
 
-fn parse_input(input: &[String]) -> Vec<Block> {
-  let line = &input[0];
-  line
+fn parse_input(input: &[String]) -> Result<Vec<Block>, SolveError> {
+  let Some(line) = input.first() else {
+    return Err(SolveError::parse(0, "expected a disk map"));
+  };
+
+  let values = line
     .chars()
-    .map(|char| char.to_string().parse::<usize>().unwrap())
+    .enumerate()
+    .map(|(col_idx, char)| {
+      char.to_digit(10).map(Convert::convert_x).ok_or_else(|| {
+        SolveError::parse(0, format!("expected a digit, found '{char}'")).at_column(col_idx)
+      })
+    })
+    .collect::<Result<Vec<usize>, SolveError>>()?;
+
+  let blocks = values
+    .into_iter()
     .enumerate()
     .map(|(idx, value)| {
       if idx % 2 == 0 {
@@ -41,7 +58,9 @@ fn parse_input(input: &[String]) -> Vec<Block> {
         }
       }
     })
-    .collect()
+    .collect();
+
+  Ok(blocks)
 }
 
 fn expand(input: &[Block]) -> Vec<usize> {
@@ -69,22 +88,29 @@ fn calculate_answer(input: &[usize]) -> usize {
 
 pub struct Day9 {}
 
+pub const SOLUTION: Solution = Solution::new(2024, 9, &Day9 {});
+
 impl AOCDay for Day9 {
+  type Input = Vec<Block>;
+
   fn name(&self) -> String {
     "day9".to_string()
   }
 
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+  fn test_answer_part1(&self) -> Option<String> {
+    Some(PART_1_EXAMPLE.to_string())
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    Some(PART_2_EXAMPLE.to_string())
   }
 
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
 
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    let mut expanded = expand(&input);
+  fn solve_part1(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let mut expanded = expand(input);
 
     let mut first_free_block_idx = expanded.iter().position(|&block| block == MARKER).unwrap();
     let mut last_file_block_idx = expanded.iter().rposition(|&block| block != MARKER).unwrap();
@@ -101,11 +127,11 @@ impl AOCDay for Day9 {
       }
     }
 
-    calculate_answer(&expanded).to_string()
+    Ok(calculate_answer(&expanded).to_string())
   }
 
-  fn solve_part2(&self, input: &[String]) -> String {
-    let mut input = parse_input(input);
+  fn solve_part2(&self, input: &Self::Input, _params: &Params) -> Answer {
+    let mut input = input.clone();
 
     loop {
       match input
@@ -120,33 +146,31 @@ impl AOCDay for Day9 {
             block.r#type == BlockType::FreeSpace
               && block.length >= input[last_file_block_idx].length
           }) {
-            None => {}
-            Some(first_free_block_idx) => {
-              if first_free_block_idx < last_file_block_idx {
-                input.swap(first_free_block_idx, last_file_block_idx);
-
-                let new_free_block_idx = last_file_block_idx;
-                let new_file_block_idx = first_free_block_idx;
-
-                if input[new_free_block_idx].length > input[new_file_block_idx].length {
-                  let remaining_free_block = Block {
-                    id: 0,
-                    length: input[new_free_block_idx].length - input[new_file_block_idx].length,
-                    r#type: BlockType::FreeSpace,
-                    compacted: false,
-                  };
-                  input[new_free_block_idx].length = input[new_file_block_idx].length;
-                  input.insert(first_free_block_idx + 1, remaining_free_block);
-                }
+            Some(first_free_block_idx) if first_free_block_idx < last_file_block_idx => {
+              input.swap(first_free_block_idx, last_file_block_idx);
+
+              let new_free_block_idx = last_file_block_idx;
+              let new_file_block_idx = first_free_block_idx;
+
+              if input[new_free_block_idx].length > input[new_file_block_idx].length {
+                let remaining_free_block = Block {
+                  id: 0,
+                  length: input[new_free_block_idx].length - input[new_file_block_idx].length,
+                  r#type: BlockType::FreeSpace,
+                  compacted: false,
+                };
+                input[new_free_block_idx].length = input[new_file_block_idx].length;
+                input.insert(first_free_block_idx + 1, remaining_free_block);
               }
             }
+            None | Some(_) => {}
           }
         }
       }
     }
 
     let expanded = expand(&input);
-    calculate_answer(&expanded).to_string()
+    Ok(calculate_answer(&expanded).to_string())
   }
 }
 
@@ -160,7 +184,12 @@ mod tests {
     let day = Day9 {};
     assert_eq!(
       PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/day9/test1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day9/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -169,7 +198,12 @@ mod tests {
     let day = Day9 {};
     assert_eq!(
       "6288707484810",
-      day.solve_part1(&read_file("input/day9/part1.txt"))
+      day
+        .solve_part1(
+          &day.parse(&read_file("input/day9/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -178,7 +212,12 @@ mod tests {
     let day = Day9 {};
     assert_eq!(
       PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/day9/test1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day9/test1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 
@@ -187,7 +226,12 @@ mod tests {
     let day = Day9 {};
     assert_eq!(
       "6311837662089",
-      day.solve_part2(&read_file("input/day9/part1.txt"))
+      day
+        .solve_part2(
+          &day.parse(&read_file("input/day9/part1.txt")).unwrap(),
+          &Params::default()
+        )
+        .unwrap()
     );
   }
 }
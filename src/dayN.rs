@@ -1,39 +1,49 @@
-use std::collections::HashSet;
-use strum_macros::EnumIter;
-use regex::Regex;
-
+use crate::error::{Answer, SolveError};
+use crate::params::Params;
+use crate::registry::Solution;
 use crate::AOCDay;
 
-const PART_1_EXAMPLE: &str = "FAIL";
-const PART_2_EXAMPLE: &str = "FAIL";
-
-fn parse_input(input: &[String]) -> Vec<i32> {
-  input.iter().map(|line| line.parse().unwrap()).collect()
+fn parse_input(input: &[String]) -> Result<Vec<i32>, SolveError> {
+  input
+    .iter()
+    .enumerate()
+    .map(|(line_idx, line)| {
+      line
+        .parse()
+        .map_err(|msg| SolveError::parse(line_idx, format!("failed to parse line: {msg}")))
+    })
+    .collect()
 }
 
 pub struct DayN {}
 
+pub const SOLUTION: Solution = Solution::new(2024, N, &DayN {});
+
 impl AOCDay for DayN {
+  type Input = Vec<i32>;
+
   fn name(&self) -> String {
     "dayN".to_string()
   }
-  
-  fn test_answer_part1(&self) -> String {
-    PART_1_EXAMPLE.to_string()
+
+  fn test_answer_part1(&self) -> Option<String> {
+    None
   }
-  
-  fn test_answer_part2(&self) -> String {
-    PART_2_EXAMPLE.to_string()
+
+  fn test_answer_part2(&self) -> Option<String> {
+    None
   }
-  
-  fn solve_part1(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    "Not implemented".to_string()
+
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError> {
+    parse_input(input)
   }
-  
-  fn solve_part2(&self, input: &[String]) -> String {
-    let input = parse_input(input);
-    "Not implemented".to_string()
+
+  fn solve_part1(&self, _input: &Self::Input, _params: &Params) -> Answer {
+    Err(SolveError::NotImplemented)
+  }
+
+  fn solve_part2(&self, _input: &Self::Input, _params: &Params) -> Answer {
+    Err(SolveError::NotImplemented)
   }
 }
 
@@ -42,21 +52,29 @@ mod tests {
   use super::*;
   use crate::utils::read_file;
 
+  // These pass until the parts are solved, so a new day doesn't break the build. Check each
+  // part's answers here once it's done.
   #[test]
   fn test_part_1_example() {
     let day = DayN {};
     assert_eq!(
-      PART_1_EXAMPLE,
-      day.solve_part1(&read_file("input/dayN/test1.txt"))
+      Err(SolveError::NotImplemented),
+      day.solve_part1(
+        &day.parse(&read_file("input/dayN/test1.txt")).unwrap(),
+        &Params::default()
+      )
     );
   }
-  
+
   #[test]
   fn test_part_1() {
     let day = DayN {};
     assert_eq!(
-      "TODO",
-      day.solve_part1(&read_file("input/dayN/part1.txt"))
+      Err(SolveError::NotImplemented),
+      day.solve_part1(
+        &day.parse(&read_file("input/dayN/part1.txt")).unwrap(),
+        &Params::default()
+      )
     );
   }
 
@@ -64,17 +82,23 @@ mod tests {
   fn test_part_2_example() {
     let day = DayN {};
     assert_eq!(
-      PART_2_EXAMPLE,
-      day.solve_part2(&read_file("input/dayN/test1.txt"))
+      Err(SolveError::NotImplemented),
+      day.solve_part2(
+        &day.parse(&read_file("input/dayN/test1.txt")).unwrap(),
+        &Params::default()
+      )
     );
   }
-  
+
   #[test]
   fn test_part_2() {
     let day = DayN {};
     assert_eq!(
-      "TODO",
-      day.solve_part2(&read_file("input/dayN/part1.txt"))
+      Err(SolveError::NotImplemented),
+      day.solve_part2(
+        &day.parse(&read_file("input/dayN/part1.txt")).unwrap(),
+        &Params::default()
+      )
     );
   }
-}
\ No newline at end of file
+}
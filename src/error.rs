@@ -0,0 +1,159 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Why a part couldn't produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+  /// The input couldn't be read.
+  Input(String),
+  /// The input couldn't be parsed. `line` and `column` are 1-based.
+  Parse {
+    line: usize,
+    column: Option<usize>,
+    message: String,
+  },
+  /// A param was given, but isn't valid for the solver.
+  InvalidParam {
+    name: String,
+    value: String,
+  },
+  /// A param was given that the solver doesn't read, with the ones it does.
+  UnknownParam {
+    name: String,
+    known: Vec<&'static str>,
+  },
+  /// The input was understood, but has no answer.
+  NoSolution(String),
+  /// The part can't be solved for this input, e.g. an example that only demonstrates the other
+  /// part.
+  NotApplicable(String),
+  NotImplemented,
+  /// A part other than 1 or 2 was asked for.
+  InvalidPart(u8),
+  /// The input given to `Solver::solve` was parsed by a different day.
+  WrongInput,
+  /// The solver panicked. Only the runner produces this, solvers should return one of the others.
+  Panicked(String),
+  /// The part didn't finish within its `--timeout`. Only the runner produces this.
+  TimedOut(Duration),
+  /// The part was cancelled, so the solver gave up.
+  Cancelled,
+}
+
+pub type Answer = Result<String, SolveError>;
+
+impl SolveError {
+  /// A parse error for the line at `line_idx` (0-based) of the input.
+  pub fn parse(line_idx: usize, message: impl Into<String>) -> Self {
+    SolveError::Parse {
+      line: line_idx + 1,
+      column: None,
+      message: message.into(),
+    }
+  }
+
+  /// Adds the 0-based column `col_idx` to a parse error.
+  #[must_use]
+  pub fn at_column(self, col_idx: usize) -> Self {
+    match self {
+      SolveError::Parse { line, message, .. } => SolveError::Parse {
+        line,
+        column: Some(col_idx + 1),
+        message,
+      },
+      other => other,
+    }
+  }
+
+  /// Moves a parse error down by `line_offset` lines, for one found while parsing a later part of
+  /// the input on its own.
+  #[must_use]
+  pub fn below(self, line_offset: usize) -> Self {
+    match self {
+      SolveError::Parse {
+        line,
+        column,
+        message,
+      } => SolveError::Parse {
+        line: line + line_offset,
+        column,
+        message,
+      },
+      other => other,
+    }
+  }
+
+  /// Whether this means the part should be skipped, rather than that it went wrong.
+  pub fn is_skip(&self) -> bool {
+    matches!(
+      self,
+      SolveError::NotApplicable(_) | SolveError::NotImplemented
+    )
+  }
+}
+
+impl fmt::Display for SolveError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SolveError::Parse {
+        line,
+        column: Some(column),
+        message,
+      } => write!(f, "parse error at line {line}, column {column}: {message}"),
+      SolveError::Parse {
+        line,
+        column: None,
+        message,
+      } => write!(f, "parse error at line {line}: {message}"),
+      SolveError::Input(message) => write!(f, "couldn't read input: {message}"),
+      SolveError::InvalidParam { name, value } => write!(f, "invalid param {name}={value}"),
+      SolveError::UnknownParam { name, known } => match known.split_last() {
+        None => write!(f, "unknown param {name}, the day doesn't take any"),
+        Some((last, [])) => write!(f, "unknown param {name}, expected {last}"),
+        Some((last, rest)) => write!(
+          f,
+          "unknown param {name}, expected {} or {last}",
+          rest.join(", ")
+        ),
+      },
+      SolveError::NoSolution(reason) => write!(f, "no solution: {reason}"),
+      SolveError::NotApplicable(reason) => write!(f, "not applicable: {reason}"),
+      SolveError::NotImplemented => write!(f, "not implemented"),
+      SolveError::InvalidPart(part) => write!(f, "there's no part {part}, expected 1 or 2"),
+      SolveError::WrongInput => write!(f, "the input was parsed by a different day"),
+      SolveError::Panicked(message) => write!(f, "panicked: {message}"),
+      SolveError::TimedOut(limit) => write!(f, "timed out after {limit:?}"),
+      SolveError::Cancelled => write!(f, "cancelled"),
+    }
+  }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_error_display() {
+    assert_eq!(
+      SolveError::parse(2, "bad number").to_string(),
+      "parse error at line 3: bad number"
+    );
+    assert_eq!(
+      SolveError::parse(0, "bad number").at_column(4).to_string(),
+      "parse error at line 1, column 5: bad number"
+    );
+    assert_eq!(
+      SolveError::parse(0, "bad number").at_column(4).below(10),
+      SolveError::parse(10, "bad number").at_column(4)
+    );
+  }
+
+  #[test]
+  fn test_is_skip() {
+    assert!(SolveError::NotImplemented.is_skip());
+    assert!(SolveError::NotApplicable("no example".to_string()).is_skip());
+    assert!(!SolveError::NoSolution("no path".to_string()).is_skip());
+  }
+}
@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::params::Params;
+use crate::Solver;
+
+pub const MANIFEST: &str = "examples.toml";
+
+/// The examples for a day, read from `input/dayN/examples.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Manifest {
+  #[serde(rename = "example", default)]
+  pub examples: Vec<Example>,
+}
+
+/// An example input, with the answers it should give.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Example {
+  /// The example's file, relative to the day's input directory.
+  pub file: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub part1: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub part2: Option<String>,
+  /// Settings the example needs that differ from the real puzzle, e.g. a smaller grid.
+  #[serde(default, skip_serializing_if = "Params::is_empty")]
+  pub params: Params,
+}
+
+impl Example {
+  pub fn expected(&self, part: u8) -> Option<&str> {
+    match part {
+      1 => self.part1.as_deref(),
+      _ => self.part2.as_deref(),
+    }
+  }
+
+  /// The example's file, and its params if it has any.
+  pub fn label(&self) -> String {
+    if self.params.is_empty() {
+      return self.file.clone();
+    }
+
+    format!("{} ({})", self.file, self.params)
+  }
+}
+
+impl Manifest {
+  pub fn parse(contents: &str) -> Result<Self, String> {
+    toml::from_str(contents).map_err(|err| err.to_string())
+  }
+
+  pub fn to_toml(&self) -> String {
+    toml::to_string_pretty(self).expect("A manifest is always valid TOML")
+  }
+
+  /// Reads the manifest in `day_dir`. Days without one fall back to `test1.txt`/`test2.txt` and the
+  /// solver's `test_answer_part1`/`test_answer_part2`.
+  pub fn load(day_dir: &Path, solver: &dyn Solver) -> Result<Self, String> {
+    let path = day_dir.join(MANIFEST);
+    if !path.exists() {
+      return Ok(Manifest::fallback(day_dir, solver));
+    }
+
+    let contents = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+    Manifest::parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+  }
+
+  fn fallback(day_dir: &Path, solver: &dyn Solver) -> Self {
+    let example = |file: &str, part1: Option<String>, part2: Option<String>| Example {
+      file: file.to_string(),
+      part1,
+      part2,
+      params: Params::default(),
+    };
+
+    let examples = if day_dir.join("test2.txt").exists() {
+      vec![
+        example("test1.txt", solver.test_answer_part1(), None),
+        example("test2.txt", None, solver.test_answer_part2()),
+      ]
+    } else {
+      vec![example(
+        "test1.txt",
+        solver.test_answer_part1(),
+        solver.test_answer_part2(),
+      )]
+    };
+
+    Manifest { examples }
+  }
+
+  /// The examples that have an expected answer for `part`.
+  pub fn for_part(&self, part: u8) -> impl Iterator<Item = &Example> {
+    self
+      .examples
+      .iter()
+      .filter(move |example| example.expected(part).is_some())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils;
+
+  #[test]
+  fn test_parse() {
+    let manifest = Manifest::parse(
+      r#"
+        [[example]]
+        file = "test1.txt"
+        part1 = "2024"
+
+        [[example]]
+        file = "test1_easy.txt"
+        part1 = "4"
+        part2 = "z00"
+
+        [example.params]
+        steps = 12
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(manifest.examples.len(), 2);
+    assert_eq!(manifest.for_part(1).count(), 2);
+    assert_eq!(manifest.for_part(2).count(), 1);
+    assert_eq!(manifest.examples[1].expected(2), Some("z00"));
+    assert_eq!(manifest.examples[1].params.get("steps"), Ok(Some(12)));
+    assert_eq!(manifest.examples[0].label(), "test1.txt");
+    assert_eq!(manifest.examples[1].label(), "test1_easy.txt (steps=12)");
+  }
+
+  #[test]
+  fn test_parse_error() {
+    assert!(Manifest::parse("[[example]]\npart1 = \"4\"").is_err());
+  }
+
+  #[test]
+  fn test_fallback() {
+    // Day 25 has no part 2, so its example has no part 2 answer to check
+    let manifest = Manifest::load(Path::new("no/such/day"), &crate::day25::Day25 {}).unwrap();
+    assert_eq!(manifest.examples.len(), 1);
+    assert_eq!(manifest.examples[0].expected(1), Some("3"));
+    assert_eq!(manifest.for_part(2).count(), 0);
+  }
+
+  #[test]
+  fn test_all_examples() {
+    for solution in crate::SOLUTIONS {
+      let solver = solution.solver;
+      let day_dir = Path::new("input").join(solver.name());
+      let manifest = Manifest::load(&day_dir, solver).unwrap();
+
+      for example in &manifest.examples {
+        let path = day_dir.join(&example.file);
+        let input = utils::read_input(&path.display().to_string()).unwrap();
+        let parsed = solver.parse(&input).unwrap();
+
+        for part in [1, 2] {
+          if let Some(expected) = example.expected(part) {
+            assert_eq!(
+              solver
+                .solve(part, parsed.as_ref(), &example.params)
+                .as_deref(),
+              Ok(expected),
+              "{} part {part}",
+              path.display()
+            );
+          }
+        }
+      }
+    }
+  }
+}
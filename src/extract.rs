@@ -0,0 +1,279 @@
+//! Pulls the examples out of a saved puzzle page, so they don't have to be copied out by hand.
+
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::examples::{Example, Manifest, MANIFEST};
+use crate::params::Params;
+
+/// A `<pre><code>` block from the page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+  /// The part whose description the block is in.
+  pub part: u8,
+  pub text: String,
+}
+
+/// An emphasized value, `<code><em>..</em></code>`, which is how the page marks answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Emphasis {
+  pub part: u8,
+  pub text: String,
+  /// The block the value follows, which is most likely the one it's the answer for.
+  pub block: Option<usize>,
+}
+
+/// Everything found on a puzzle page.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Page {
+  pub blocks: Vec<Block>,
+  pub emphasized: Vec<Emphasis>,
+}
+
+fn unescape(html: &str) -> String {
+  static TAG: OnceLock<Regex> = OnceLock::new();
+  let tag = TAG.get_or_init(|| Regex::new(r"<[^>]*>").unwrap());
+  tag
+    .replace_all(html, "")
+    .replace("&lt;", "<")
+    .replace("&gt;", ">")
+    .replace("&quot;", "\"")
+    .replace("&#39;", "'")
+    .replace("&amp;", "&")
+}
+
+/// Finds the blocks and emphasized values in each part's `<article>`. Part 2's description is only
+/// on the page once part 1 is solved, and any articles after it aren't part of the puzzle.
+pub fn parse(html: &str) -> Page {
+  static ARTICLE: OnceLock<Regex> = OnceLock::new();
+  static ITEM: OnceLock<Regex> = OnceLock::new();
+  let article = ARTICLE.get_or_init(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+  let item = ITEM.get_or_init(|| {
+    Regex::new(r"(?s)<pre><code>(?<block>.*?)</code></pre>|<code><em>(?<code_em>.*?)</em></code>|<em><code>(?<em_code>.*?)</code></em>").unwrap()
+  });
+
+  let mut page = Page::default();
+  for (part, article) in (1..).zip(article.captures_iter(html).take(2)) {
+    for captures in item.captures_iter(&article[1]) {
+      if let Some(block) = captures.name("block") {
+        page.blocks.push(Block {
+          part,
+          text: unescape(block.as_str()),
+        });
+        continue;
+      }
+
+      let text = captures
+        .name("code_em")
+        .or_else(|| captures.name("em_code"))
+        .map(|text| unescape(text.as_str()))
+        .unwrap_or_default();
+      page.emphasized.push(Emphasis {
+        part,
+        text,
+        block: page.blocks.len().checked_sub(1),
+      });
+    }
+  }
+  page
+}
+
+impl Page {
+  /// The examples on the page. Each multi-line block is taken to be an example, with the last value
+  /// emphasized after it in each part as its answer. Part 2 usually reuses part 1's examples before
+  /// giving any of its own, so its answers that come before its first block go to the last part 1
+  /// example.
+  pub fn examples(&self) -> Vec<(usize, Option<String>, Option<String>)> {
+    let mut answers: Vec<(usize, [Option<String>; 2])> = Vec::new();
+    let is_example = |idx: usize| self.blocks[idx].text.trim_end().contains('\n');
+
+    for emphasis in &self.emphasized {
+      let block = match emphasis.block {
+        Some(block) if self.blocks[block].part == emphasis.part || emphasis.part == 1 => {
+          Some(block)
+        }
+        // Before part 2's first block, so it's about one of part 1's
+        _ => answers
+          .iter()
+          .rev()
+          .find(|(_, [part1, _])| part1.is_some())
+          .map(|(block, _)| *block),
+      };
+      let Some(block) = block.filter(|&block| is_example(block)) else {
+        continue;
+      };
+
+      let idx = answers
+        .iter()
+        .position(|(b, _)| *b == block)
+        .unwrap_or_else(|| {
+          answers.push((block, [None, None]));
+          answers.len() - 1
+        });
+      answers[idx].1[usize::from(emphasis.part - 1)] = Some(emphasis.text.clone());
+    }
+
+    answers
+      .into_iter()
+      .map(|(block, [part1, part2])| (block, part1, part2))
+      .collect()
+  }
+}
+
+/// Writes each example to `testN.txt` in `day_dir`, and a manifest listing them. Existing files are
+/// only replaced with `force`.
+pub fn write(page: &Page, day_dir: &Path, force: bool) -> io::Result<Vec<PathBuf>> {
+  let examples = page.examples();
+  let files = (1..=examples.len())
+    .map(|n| format!("test{n}.txt"))
+    .collect::<Vec<String>>();
+
+  if !force {
+    for file in files.iter().map(String::as_str).chain([MANIFEST]) {
+      let path = day_dir.join(file);
+      if path.exists() {
+        return Err(io::Error::new(
+          io::ErrorKind::AlreadyExists,
+          format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+          ),
+        ));
+      }
+    }
+  }
+
+  fs::create_dir_all(day_dir)?;
+  let mut written = Vec::new();
+  let mut manifest = Manifest::default();
+  for (file, (block, part1, part2)) in files.into_iter().zip(examples) {
+    let path = day_dir.join(&file);
+    fs::write(&path, &page.blocks[block].text)?;
+    written.push(path);
+    manifest.examples.push(Example {
+      file,
+      part1,
+      part2,
+      params: Params::default(),
+    });
+  }
+
+  let path = day_dir.join(MANIFEST);
+  fs::write(&path, manifest.to_toml())?;
+  written.push(path);
+  Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::temp_dir::TempDir;
+
+  const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+2   5
+</code></pre>
+<p>The smallest number in the left list is <code>1</code>, a distance of <code><em>2</em></code>.</p>
+<p>In the example above, this is <code>2 + 1 + 0</code>, a total distance of <code><em>11</em></code>!</p>
+</article>
+<p>Your puzzle answer was <code>1882714</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>So, for these example lists, the similarity score at the end of this process is <code><em>31</em></code>.</p>
+<p>Here's a larger example with <code>&lt;</code> in it:</p>
+<pre><code>a &lt; <em>b</em>
+c &amp; d
+</code></pre>
+<p>That gives <em><code>7</code></em>.</p>
+<pre><code>x</code></pre>
+</article>
+</main>"#;
+
+  #[test]
+  fn test_parse() {
+    let page = parse(PAGE);
+    assert_eq!(
+      page.blocks,
+      vec![
+        Block {
+          part: 1,
+          text: "3   4\n4   3\n2   5\n".to_string()
+        },
+        Block {
+          part: 2,
+          text: "a < b\nc & d\n".to_string()
+        },
+        Block {
+          part: 2,
+          text: "x".to_string()
+        },
+      ]
+    );
+    assert_eq!(
+      page
+        .emphasized
+        .iter()
+        .map(|emphasis| (emphasis.part, emphasis.text.as_str(), emphasis.block))
+        .collect::<Vec<_>>(),
+      vec![
+        (1, "2", Some(0)),
+        (1, "11", Some(0)),
+        (2, "31", Some(0)),
+        (2, "7", Some(1))
+      ]
+    );
+
+    assert_eq!(
+      page.examples(),
+      vec![
+        (0, Some("11".to_string()), Some("31".to_string())),
+        (1, None, Some("7".to_string())),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_parse_extra_article() {
+    let html = PAGE.replace(
+      "</main>",
+      "<article><p>Share this: <code><em>42</em></code></p><pre><code>1\n2\n</code></pre></article></main>",
+    );
+    let page = parse(&html);
+
+    assert_eq!(page, parse(PAGE));
+    assert_eq!(page.examples().len(), 2);
+  }
+
+  #[test]
+  fn test_write() {
+    let dir = TempDir::new("extract");
+    let page = parse(PAGE);
+
+    let written = write(&page, &dir, false).unwrap();
+    assert_eq!(
+      written,
+      vec![
+        dir.join("test1.txt"),
+        dir.join("test2.txt"),
+        dir.join(MANIFEST)
+      ]
+    );
+    assert_eq!(
+      fs::read_to_string(dir.join("test2.txt")).unwrap(),
+      "a < b\nc & d\n"
+    );
+
+    let manifest = Manifest::parse(&fs::read_to_string(dir.join(MANIFEST)).unwrap()).unwrap();
+    assert_eq!(manifest.examples.len(), 2);
+    assert_eq!(manifest.examples[0].expected(2), Some("31"));
+    assert_eq!(manifest.examples[1].expected(1), None);
+
+    assert!(write(&page, &dir, false).is_err());
+    assert!(write(&page, &dir, true).is_ok());
+  }
+}
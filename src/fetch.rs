@@ -0,0 +1,190 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::client::{Client, ClientError};
+
+/// Records when an input was last downloaded, so separate runs share the throttle.
+pub const LAST_FETCH: &str = ".last_fetch";
+/// How long to wait between downloads, unless `--throttle` says otherwise.
+pub const DEFAULT_THROTTLE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+  Client(ClientError),
+  Io(String),
+}
+
+impl fmt::Display for FetchError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      FetchError::Client(err) => write!(f, "{err}"),
+      FetchError::Io(message) => write!(f, "{message}"),
+    }
+  }
+}
+
+impl From<ClientError> for FetchError {
+  fn from(err: ClientError) -> Self {
+    FetchError::Client(err)
+  }
+}
+
+/// Where an input came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+  /// The input was already there, so nothing was downloaded.
+  Cached(PathBuf),
+  Downloaded(PathBuf),
+}
+
+/// Keeps downloads at least `interval` apart, across runs, by noting the time of the last one in
+/// `input/.last_fetch`.
+pub struct Throttle {
+  interval: Duration,
+  path: PathBuf,
+}
+
+impl Throttle {
+  pub fn new(input_dir: &Path, interval: Duration) -> Self {
+    Throttle {
+      interval,
+      path: input_dir.join(LAST_FETCH),
+    }
+  }
+
+  fn last(&self) -> Option<SystemTime> {
+    fs::metadata(&self.path).ok()?.modified().ok()
+  }
+
+  /// How long until the next download is allowed.
+  pub fn remaining(&self) -> Duration {
+    self
+      .last()
+      .and_then(|last| last.elapsed().ok())
+      .map_or(Duration::ZERO, |elapsed| {
+        self.interval.saturating_sub(elapsed)
+      })
+  }
+
+  pub fn wait(&self) {
+    let remaining = self.remaining();
+    if !remaining.is_zero() {
+      thread::sleep(remaining);
+    }
+  }
+
+  pub fn record(&self) -> Result<(), FetchError> {
+    fs::write(&self.path, "")
+      .map_err(|err| FetchError::Io(format!("{}: {err}", self.path.display())))
+  }
+}
+
+/// Where the real input for `day` is kept.
+pub fn input_path(input_dir: &Path, day: u8) -> PathBuf {
+  input_dir.join(format!("day{day}")).join("part1.txt")
+}
+
+/// Whether the input at `path` has been downloaded. `new` leaves an empty file to be filled in, and
+/// that doesn't count.
+pub fn is_cached(path: &Path) -> bool {
+  fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Downloads the input for `day` into `input_dir`, unless it's already there.
+pub fn fetch(
+  client: &Client,
+  throttle: &Throttle,
+  input_dir: &Path,
+  year: u16,
+  day: u8,
+) -> Result<Fetched, FetchError> {
+  let path = input_path(input_dir, day);
+  if is_cached(&path) {
+    return Ok(Fetched::Cached(path));
+  }
+
+  let io_error = |err: std::io::Error| FetchError::Io(format!("{}: {err}", path.display()));
+  fs::create_dir_all(path.parent().unwrap()).map_err(io_error)?;
+
+  throttle.wait();
+  let input = client.fetch_input(year, day);
+  throttle.record()?;
+
+  // Written under another name first, so an interrupted download isn't mistaken for the input
+  let partial = path.with_extension("part");
+  fs::write(&partial, input?).map_err(io_error)?;
+  fs::rename(&partial, &path).map_err(io_error)?;
+  Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock_server::MockServer;
+  use crate::temp_dir::TempDir;
+
+  #[test]
+  fn test_fetch() {
+    let dir = TempDir::new("fetch");
+    let server = MockServer::start(vec![
+      (200, "1 2\n3 4\n"),
+      (404, "Not found"),
+      (200, "5 6\n"),
+    ]);
+    let client = Client::new(&server.url, "abc123");
+    let throttle = Throttle::new(&dir, Duration::ZERO);
+
+    let path = dir.join("day1/part1.txt");
+    assert_eq!(
+      fetch(&client, &throttle, &dir, 2024, 1),
+      Ok(Fetched::Downloaded(path.clone()))
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+    // Already downloaded, so the site isn't asked again
+    assert_eq!(
+      fetch(&client, &throttle, &dir, 2024, 1),
+      Ok(Fetched::Cached(path))
+    );
+
+    assert!(matches!(
+      fetch(&client, &throttle, &dir, 2024, 2),
+      Err(FetchError::Client(ClientError::Status { code: 404, .. }))
+    ));
+    assert!(!input_path(&dir, 2).exists());
+
+    // The empty file `new` leaves behind isn't the input
+    let path = input_path(&dir, 3);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, "").unwrap();
+    assert!(!is_cached(&path));
+    assert_eq!(
+      fetch(&client, &throttle, &dir, 2024, 3),
+      Ok(Fetched::Downloaded(path.clone()))
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "5 6\n");
+
+    let requests = server.finish();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2024/day/1/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    assert_eq!(requests[1].path, "/2024/day/2/input");
+  }
+
+  #[test]
+  fn test_throttle() {
+    let dir = TempDir::new("throttle");
+    let throttle = Throttle::new(&dir, Duration::from_secs(3600));
+    assert_eq!(throttle.remaining(), Duration::ZERO);
+
+    throttle.record().unwrap();
+    assert!(throttle.remaining() > Duration::from_secs(3500));
+    assert_eq!(
+      Throttle::new(&dir, Duration::ZERO).remaining(),
+      Duration::ZERO
+    );
+  }
+}
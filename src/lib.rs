@@ -0,0 +1,154 @@
+//! Advent of Code 2024 solutions. Each `dayN` module has a `DayN` solver, and `SOLUTIONS` lists
+//! all of them for the runner.
+//!
+//! ```
+//! use advent2024::day1::Day1;
+//! use advent2024::params::Params;
+//! use advent2024::Solver;
+//!
+//! let answer = Day1 {}.solve_str(1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3", &Params::default());
+//! assert_eq!(answer.as_deref(), Ok("11"));
+//! ```
+
+// The library is only here so the binary, the tests and the benches can share the solvers. It isn't
+// published, so its functions don't need the docs and attributes a public API would.
+#![allow(
+  clippy::missing_errors_doc,
+  clippy::missing_panics_doc,
+  clippy::must_use_candidate
+)]
+
+use std::any::Any;
+
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod cli;
+pub mod client;
+pub mod error;
+pub mod examples;
+pub mod extract;
+pub mod fetch;
+pub mod logging;
+pub mod params;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod utils;
+pub mod watch;
+
+#[cfg(test)]
+mod mock_server;
+#[cfg(test)]
+mod temp_dir;
+
+use error::{Answer, SolveError};
+use params::Params;
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+pub trait AOCDay: Sync {
+  /// The puzzle input once it's been parsed, shared by both parts.
+  type Input: Send + Sync + 'static;
+
+  fn name(&self) -> String;
+  /// The answers to `test1.txt`, used when a day has no examples manifest. `None` where the
+  /// example has no answer for that part.
+  fn test_answer_part1(&self) -> Option<String>;
+  fn test_answer_part2(&self) -> Option<String>;
+  /// The names of the params the solver reads. Any others are rejected, so a misspelled one isn't
+  /// silently ignored.
+  fn params(&self) -> &'static [&'static str] {
+    &[]
+  }
+
+  fn parse(&self, input: &[String]) -> Result<Self::Input, SolveError>;
+  fn solve_part1(&self, input: &Self::Input, params: &Params) -> Answer;
+  fn solve_part2(&self, input: &Self::Input, params: &Params) -> Answer;
+}
+
+/// An `AOCDay` with its input type hidden, so days with different inputs can be registered
+/// together.
+pub trait Solver: Sync {
+  fn name(&self) -> String;
+  fn test_answer_part1(&self) -> Option<String>;
+  fn test_answer_part2(&self) -> Option<String>;
+
+  fn parse(&self, input: &[String]) -> Result<Box<dyn Any + Send + Sync>, SolveError>;
+  /// Solves `part`, 1 or 2, given input returned by this solver's `parse`.
+  fn solve(&self, part: u8, input: &dyn Any, params: &Params) -> Answer;
+
+  /// Parses and solves `part` straight from the puzzle text.
+  fn solve_str(&self, part: u8, input: &str, params: &Params) -> Answer {
+    let input = self.parse(&utils::lines(input))?;
+    self.solve(part, input.as_ref(), params)
+  }
+}
+
+impl<T: AOCDay> Solver for T {
+  fn name(&self) -> String {
+    AOCDay::name(self)
+  }
+
+  fn test_answer_part1(&self) -> Option<String> {
+    AOCDay::test_answer_part1(self)
+  }
+
+  fn test_answer_part2(&self) -> Option<String> {
+    AOCDay::test_answer_part2(self)
+  }
+
+  fn parse(&self, input: &[String]) -> Result<Box<dyn Any + Send + Sync>, SolveError> {
+    let input = AOCDay::parse(self, input)?;
+    Ok(Box::new(input))
+  }
+
+  fn solve(&self, part: u8, input: &dyn Any, params: &Params) -> Answer {
+    let input = input
+      .downcast_ref::<T::Input>()
+      .ok_or(SolveError::WrongInput)?;
+    params.check_known(AOCDay::params(self))?;
+    match part {
+      1 => self.solve_part1(input, params),
+      2 => self.solve_part2(input, params),
+      _ => Err(SolveError::InvalidPart(part)),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_solve() {
+    let day1 = &day1::Day1 {} as &dyn Solver;
+    let input = day1.parse(&utils::lines("3   4\n1   3\n")).unwrap();
+    assert_eq!(
+      day1.solve(1, input.as_ref(), &Params::default()),
+      Ok("3".to_string())
+    );
+    assert_eq!(
+      day1.solve(3, input.as_ref(), &Params::default()),
+      Err(SolveError::InvalidPart(3))
+    );
+
+    assert_eq!(
+      day1.solve(1, input.as_ref(), &Params::from([("size", "7")])),
+      Err(SolveError::UnknownParam {
+        name: "size".to_string(),
+        known: vec![]
+      })
+    );
+
+    let day2_input = (&day2::Day2 {} as &dyn Solver)
+      .parse(&utils::lines("1 2 3\n"))
+      .unwrap();
+    assert_eq!(
+      day1.solve(1, day2_input.as_ref(), &Params::default()),
+      Err(SolveError::WrongInput)
+    );
+  }
+}
@@ -0,0 +1,78 @@
+//! Diagnostics from the solvers, logged with `tracing` and off unless asked for.
+//!
+//! Each day logs under its module's target, e.g. `advent2024::day15`, so one day can be traced
+//! without drowning in the others.
+
+use std::fs::File;
+use std::io;
+use std::sync::Mutex;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::prelude::*;
+
+use crate::cli::LogArgs;
+
+/// The target a day's events are logged under.
+pub fn day_target(day: u8) -> String {
+  format!("{}::day{day}", env!("CARGO_CRATE_NAME"))
+}
+
+/// Warnings only by default, debug with `-v` and everything with `-vv`. Days given with `--trace`
+/// log everything regardless.
+pub fn filter(args: &LogArgs) -> Targets {
+  let level = match args.verbose {
+    0 => LevelFilter::WARN,
+    1 => LevelFilter::DEBUG,
+    _ => LevelFilter::TRACE,
+  };
+
+  args
+    .trace
+    .iter()
+    .fold(Targets::new().with_default(level), |targets, &day| {
+      targets.with_target(day_target(day), LevelFilter::TRACE)
+    })
+}
+
+/// Sends the log to stderr, or to `--log-file`.
+pub fn init(args: &LogArgs) -> io::Result<()> {
+  let (writer, ansi) = match &args.log_file {
+    Some(path) => (BoxMakeWriter::new(Mutex::new(File::create(path)?)), false),
+    None => (BoxMakeWriter::new(io::stderr), true),
+  };
+
+  tracing_subscriber::registry()
+    .with(
+      tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi)
+        .without_time(),
+    )
+    .with(filter(args))
+    .try_init()
+    .map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tracing::Level;
+
+  #[test]
+  fn test_filter() {
+    let quiet = filter(&LogArgs::default());
+    assert!(quiet.would_enable(&day_target(15), &Level::WARN));
+    assert!(!quiet.would_enable(&day_target(15), &Level::DEBUG));
+
+    let args = LogArgs {
+      verbose: 1,
+      trace: vec![15],
+      log_file: None,
+    };
+    let verbose = filter(&args);
+    assert!(verbose.would_enable(&day_target(14), &Level::DEBUG));
+    assert!(!verbose.would_enable(&day_target(14), &Level::TRACE));
+    assert!(verbose.would_enable(&day_target(15), &Level::TRACE));
+    assert!(!verbose.would_enable(&day_target(1), &Level::TRACE));
+  }
+}
@@ -0,0 +1,115 @@
+//! A stand-in for the puzzle site in tests, so they never touch the network.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// A request the server received.
+#[derive(Debug, Clone)]
+pub struct Request {
+  pub method: String,
+  pub path: String,
+  pub headers: Vec<(String, String)>,
+  pub body: String,
+}
+
+impl Request {
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(header, _)| header.eq_ignore_ascii_case(name))
+      .map(|(_, value)| value.as_str())
+  }
+}
+
+/// Serves each canned `(status, body)` response once, in order, then stops.
+pub struct MockServer {
+  pub url: String,
+  requests: Arc<Mutex<Vec<Request>>>,
+  handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+  pub fn start(responses: Vec<(u16, &str)>) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Could not bind mock server");
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let responses = responses
+      .into_iter()
+      .map(|(status, body)| (status, body.to_string()))
+      .collect::<Vec<(u16, String)>>();
+    let received = Arc::clone(&requests);
+    let handle = thread::spawn(move || {
+      for (status, body) in responses {
+        let (mut stream, _) = listener.accept().expect("Could not accept connection");
+        let request = read_request(&mut BufReader::new(&stream));
+        received.lock().unwrap().push(request);
+
+        write!(
+          stream,
+          "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+          body.len()
+        )
+        .expect("Could not write response");
+      }
+    });
+
+    MockServer {
+      url,
+      requests,
+      handle: Some(handle),
+    }
+  }
+
+  /// The requests received so far.
+  pub fn requests(&self) -> Vec<Request> {
+    self.requests.lock().unwrap().clone()
+  }
+
+  /// Waits until every canned response has been served, returning the requests.
+  pub fn finish(mut self) -> Vec<Request> {
+    if let Some(handle) = self.handle.take() {
+      handle.join().expect("Mock server panicked");
+    }
+    self.requests()
+  }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Request {
+  let mut line = String::new();
+  reader.read_line(&mut line).unwrap();
+  let mut parts = line.split_whitespace();
+  let method = parts.next().unwrap_or_default().to_string();
+  let path = parts.next().unwrap_or_default().to_string();
+
+  let mut headers = Vec::new();
+  loop {
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    let line = line.trim_end();
+    if line.is_empty() {
+      break;
+    }
+    if let Some((name, value)) = line.split_once(':') {
+      headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+  }
+
+  let length = headers
+    .iter()
+    .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+    .and_then(|(_, value)| value.parse().ok())
+    .unwrap_or(0);
+  let mut body = vec![0; length];
+  reader.read_exact(&mut body).unwrap();
+
+  Request {
+    method,
+    path,
+    headers,
+    body: String::from_utf8_lossy(&body).to_string(),
+  }
+}
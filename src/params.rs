@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::SolveError;
+
+/// Settings a puzzle gives in its text rather than its input, like the size of a grid. Solvers
+/// default to the values for the real puzzle, and examples override them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<String, toml::Value>")]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The param called `name`, if it was given.
+  pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, SolveError> {
+    self
+      .0
+      .get(name)
+      .map(|value| {
+        value.parse().map_err(|_| SolveError::InvalidParam {
+          name: name.to_string(),
+          value: value.clone(),
+        })
+      })
+      .transpose()
+  }
+
+  /// The param called `name`, or `default` if it wasn't given.
+  pub fn get_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, SolveError> {
+    Ok(self.get(name)?.unwrap_or(default))
+  }
+
+  /// Errors on the first param that isn't one of `known`, the ones the solver reads.
+  pub fn check_known(&self, known: &[&'static str]) -> Result<(), SolveError> {
+    match self.0.keys().find(|name| !known.contains(&name.as_str())) {
+      Some(name) => Err(SolveError::UnknownParam {
+        name: name.clone(),
+        known: known.to_vec(),
+      }),
+      None => Ok(()),
+    }
+  }
+
+  /// Like `get_or`, for a param that has to be more than 0, like a size.
+  pub fn get_positive_or<T>(&self, name: &str, default: T) -> Result<T, SolveError>
+  where
+    T: FromStr + PartialOrd + Default,
+  {
+    let value = self.get_or(name, default)?;
+    if value <= T::default() {
+      return Err(SolveError::InvalidParam {
+        name: name.to_string(),
+        value: self.0.get(name).cloned().unwrap_or_default(),
+      });
+    }
+    Ok(value)
+  }
+}
+
+impl FromIterator<(String, String)> for Params {
+  fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+    Params(iter.into_iter().collect())
+  }
+}
+
+impl<const N: usize> From<[(&str, &str); N]> for Params {
+  fn from(params: [(&str, &str); N]) -> Self {
+    params
+      .into_iter()
+      .map(|(name, value)| (name.to_string(), value.to_string()))
+      .collect()
+  }
+}
+
+impl From<BTreeMap<String, toml::Value>> for Params {
+  fn from(params: BTreeMap<String, toml::Value>) -> Self {
+    params
+      .into_iter()
+      .map(|(name, value)| match value {
+        toml::Value::String(value) => (name, value),
+        value => (name, value.to_string()),
+      })
+      .collect()
+  }
+}
+
+impl fmt::Display for Params {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let params = self
+      .0
+      .iter()
+      .map(|(name, value)| format!("{name}={value}"))
+      .collect::<Vec<String>>();
+    write!(f, "{}", params.join(", "))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get() {
+    let params = Params::from([("width", "11"), ("name", "x")]);
+    assert_eq!(params.get::<usize>("width"), Ok(Some(11)));
+    assert_eq!(params.get::<usize>("height"), Ok(None));
+    assert_eq!(params.get_or::<usize>("height", 103), Ok(103));
+    assert_eq!(params.get_positive_or::<usize>("width", 1), Ok(11));
+    assert_eq!(
+      Params::from([("width", "0")]).get_positive_or::<isize>("width", 101),
+      Err(SolveError::InvalidParam {
+        name: "width".to_string(),
+        value: "0".to_string()
+      })
+    );
+    assert_eq!(
+      params.get::<usize>("name"),
+      Err(SolveError::InvalidParam {
+        name: "name".to_string(),
+        value: "x".to_string()
+      })
+    );
+  }
+
+  #[test]
+  fn test_check_known() {
+    let params = Params::from([("widht", "11")]);
+    assert_eq!(Params::default().check_known(&[]), Ok(()));
+    assert_eq!(
+      params.check_known(&["width", "height"]),
+      Err(SolveError::UnknownParam {
+        name: "widht".to_string(),
+        known: vec!["width", "height"]
+      })
+    );
+    assert_eq!(
+      params
+        .check_known(&["width", "height"])
+        .unwrap_err()
+        .to_string(),
+      "unknown param widht, expected width or height"
+    );
+  }
+
+  #[test]
+  fn test_from_toml() {
+    let params: Params = toml::from_str("size = 7\nmode = \"fast\"").unwrap();
+    assert_eq!(params.get::<usize>("size"), Ok(Some(7)));
+    assert_eq!(params.to_string(), "mode=fast, size=7");
+  }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+
+use crate::Solver;
+
+pub const YEAR: u16 = 2024;
+pub const DAYS_PER_YEAR: u8 = 25;
+
+/// A solver, registered against the puzzle it solves.
+pub struct Solution {
+  pub year: u16,
+  pub day: u8,
+  pub solver: &'static dyn Solver,
+}
+
+impl Solution {
+  pub const fn new(year: u16, day: u8, solver: &'static dyn Solver) -> Self {
+    Solution { year, day, solver }
+  }
+}
+
+pub struct Registry {
+  solutions: BTreeMap<(u16, u8), &'static Solution>,
+}
+
+impl Registry {
+  pub fn new(solutions: &'static [Solution]) -> Self {
+    let mut registry = Registry {
+      solutions: BTreeMap::new(),
+    };
+
+    for solution in solutions {
+      let existing = registry
+        .solutions
+        .insert((solution.year, solution.day), solution);
+      assert!(
+        existing.is_none(),
+        "{} day {} is registered more than once",
+        solution.year,
+        solution.day
+      );
+    }
+
+    registry
+  }
+
+  pub fn get(&self, year: u16, day: u8) -> Option<&'static Solution> {
+    self.solutions.get(&(year, day)).copied()
+  }
+
+  /// All registered solutions, ordered by year and then day.
+  pub fn solutions(&self) -> impl Iterator<Item = &'static Solution> + '_ {
+    self.solutions.values().copied()
+  }
+
+  pub fn years(&self) -> Vec<u16> {
+    let mut years = self
+      .solutions
+      .keys()
+      .map(|(year, _)| *year)
+      .collect::<Vec<u16>>();
+    years.dedup();
+    years
+  }
+
+  /// The days of `year` that don't have a solution yet.
+  pub fn missing(&self, year: u16) -> Vec<u8> {
+    (1..=DAYS_PER_YEAR)
+      .filter(|day| !self.solutions.contains_key(&(year, *day)))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::{Answer, SolveError};
+  use crate::params::Params;
+  use crate::AOCDay;
+
+  struct Stub {}
+
+  impl AOCDay for Stub {
+    type Input = ();
+
+    fn name(&self) -> String {
+      "stub".to_string()
+    }
+
+    fn test_answer_part1(&self) -> Option<String> {
+      None
+    }
+
+    fn test_answer_part2(&self) -> Option<String> {
+      None
+    }
+
+    fn parse(&self, _input: &[String]) -> Result<(), SolveError> {
+      Ok(())
+    }
+
+    fn solve_part1(&self, _input: &(), _params: &Params) -> Answer {
+      Err(SolveError::NotImplemented)
+    }
+
+    fn solve_part2(&self, _input: &(), _params: &Params) -> Answer {
+      Err(SolveError::NotImplemented)
+    }
+  }
+
+  static STUBS: [Solution; 3] = [
+    Solution::new(2024, 3, &Stub {}),
+    Solution::new(2023, 25, &Stub {}),
+    Solution::new(2024, 1, &Stub {}),
+  ];
+
+  #[test]
+  fn test_solutions_are_ordered() {
+    let registry = Registry::new(&STUBS);
+    let keys = registry
+      .solutions()
+      .map(|solution| (solution.year, solution.day))
+      .collect::<Vec<(u16, u8)>>();
+
+    assert_eq!(keys, vec![(2023, 25), (2024, 1), (2024, 3)]);
+    assert_eq!(registry.years(), vec![2023, 2024]);
+  }
+
+  #[test]
+  fn test_get() {
+    let registry = Registry::new(&STUBS);
+    assert!(registry.get(2024, 3).is_some());
+    assert!(registry.get(2024, 2).is_none());
+  }
+
+  #[test]
+  fn test_missing() {
+    let registry = Registry::new(&STUBS);
+    assert_eq!(registry.missing(2023), (1..=24).collect::<Vec<u8>>());
+    assert_eq!(registry.missing(2024).len(), 23);
+    assert!(!registry.missing(2024).contains(&1));
+  }
+
+  #[test]
+  fn test_all_days_registered() {
+    let registry = Registry::new(crate::SOLUTIONS);
+    assert!(registry.missing(YEAR).is_empty());
+  }
+}
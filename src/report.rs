@@ -0,0 +1,371 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{self, Write};
+use std::time::Duration;
+
+use crate::error::{Answer, SolveError};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+  /// Progress as it happens, then a summary table
+  #[default]
+  Table,
+  Json,
+  Junit,
+}
+
+impl Format {
+  /// Machine readable formats keep stdout for the report itself.
+  pub fn is_machine_readable(self) -> bool {
+    self != Format::Table
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+  /// The answer matched the expected answer.
+  Passed,
+  /// The answer didn't match the expected answer.
+  Failed,
+  /// An answer was found, but there was nothing to compare it against.
+  Solved,
+  Skipped,
+  Error,
+  /// The part didn't finish within its time limit.
+  #[serde(rename = "timeout")]
+  TimedOut,
+}
+
+impl fmt::Display for Status {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let status = match self {
+      Status::Passed => "passed",
+      Status::Failed => "failed",
+      Status::Solved => "solved",
+      Status::Skipped => "skipped",
+      Status::Error => "error",
+      Status::TimedOut => "timeout",
+    };
+    write!(f, "{status}")
+  }
+}
+
+impl Status {
+  /// Whether the part counts as a success for the exit code: it didn't fail, error or time out.
+  pub fn is_ok(self) -> bool {
+    matches!(self, Status::Passed | Status::Solved | Status::Skipped)
+  }
+}
+
+/// The outcome of solving one part of one day against one input.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+  pub day: u8,
+  pub part: u8,
+  pub input: String,
+  pub expected: Option<String>,
+  pub actual: Option<String>,
+  pub error: Option<String>,
+  pub status: Status,
+  /// Time spent parsing the input, zero if another part already parsed it.
+  #[serde(
+    rename = "parse_ms",
+    serialize_with = "serialize_millis",
+    deserialize_with = "deserialize_millis"
+  )]
+  pub parse_elapsed: Duration,
+  #[serde(
+    rename = "elapsed_ms",
+    serialize_with = "serialize_millis",
+    deserialize_with = "deserialize_millis"
+  )]
+  pub elapsed: Duration,
+}
+
+impl RunRecord {
+  pub fn new(
+    day: u8,
+    part: u8,
+    input: &str,
+    expected: Option<&str>,
+    answer: &Answer,
+    parse_elapsed: Duration,
+    elapsed: Duration,
+  ) -> Self {
+    let status = match (answer, expected) {
+      (Ok(actual), Some(expected)) if actual == expected => Status::Passed,
+      (Ok(_), Some(_)) => Status::Failed,
+      (Ok(_), None) => Status::Solved,
+      (Err(err), _) if err.is_skip() => Status::Skipped,
+      (Err(SolveError::TimedOut(_)), _) => Status::TimedOut,
+      (Err(_), _) => Status::Error,
+    };
+
+    RunRecord {
+      day,
+      part,
+      input: input.to_string(),
+      expected: expected.map(ToString::to_string),
+      actual: answer.as_ref().ok().cloned(),
+      error: answer.as_ref().err().map(SolveError::to_string),
+      status,
+      parse_elapsed,
+      elapsed,
+    }
+  }
+
+  /// The time taken to parse and solve.
+  pub fn total(&self) -> Duration {
+    self.parse_elapsed + self.elapsed
+  }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn serialize_millis<S: Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+  serializer.serialize_f64(elapsed.as_secs_f64() * 1000.0)
+}
+
+fn deserialize_millis<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+  let millis = f64::deserialize(deserializer)?;
+  Ok(Duration::from_secs_f64(millis / 1000.0))
+}
+
+/// Reads back a report rendered as JSON.
+pub fn parse_json(json: &str) -> Result<Vec<RunRecord>, String> {
+  serde_json::from_str(json).map_err(|err| err.to_string())
+}
+
+pub fn render(format: Format, records: &[RunRecord]) -> String {
+  match format {
+    Format::Table => render_table(records),
+    Format::Json => render_json(records),
+    Format::Junit => render_junit(records),
+  }
+}
+
+fn render_table(records: &[RunRecord]) -> String {
+  let headers = [
+    "day", "part", "input", "expected", "actual", "status", "parse", "time",
+  ];
+  let rows = records
+    .iter()
+    .map(|record| {
+      [
+        record.day.to_string(),
+        record.part.to_string(),
+        record.input.clone(),
+        record.expected.clone().unwrap_or_default(),
+        record
+          .actual
+          .clone()
+          .or_else(|| record.error.clone())
+          .unwrap_or_default(),
+        record.status.to_string(),
+        format!("{:?}", record.parse_elapsed),
+        format!("{:?}", record.elapsed),
+      ]
+    })
+    .collect::<Vec<[String; 8]>>();
+
+  let widths = (0..headers.len())
+    .map(|col| {
+      rows
+        .iter()
+        .map(|row| row[col].chars().count())
+        .chain([headers[col].len()])
+        .max()
+        .unwrap_or_default()
+    })
+    .collect::<Vec<usize>>();
+
+  let mut output = String::new();
+  let lines = [headers.map(ToString::to_string)].into_iter().chain(rows);
+  for row in lines {
+    let cells = row
+      .iter()
+      .zip(&widths)
+      .map(|(cell, width)| format!("{cell:<width$}"))
+      .collect::<Vec<String>>();
+    writeln!(output, "{}", cells.join("  ").trim_end()).unwrap();
+  }
+
+  output
+}
+
+fn render_json(records: &[RunRecord]) -> String {
+  let mut output = serde_json::to_string_pretty(records).expect("Records are always serializable");
+  output.push('\n');
+  output
+}
+
+fn escape_xml(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+fn render_junit(records: &[RunRecord]) -> String {
+  let count = |status: Status| records.iter().filter(|r| r.status == status).count();
+  let total_time = records.iter().map(RunRecord::total).sum::<Duration>();
+
+  let mut output = String::new();
+  writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+  writeln!(
+    output,
+    r#"<testsuites tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.6}">"#,
+    records.len(),
+    count(Status::Failed),
+    count(Status::Error) + count(Status::TimedOut),
+    count(Status::Skipped),
+    total_time.as_secs_f64()
+  )
+  .unwrap();
+
+  let mut days = records.iter().map(|r| r.day).collect::<Vec<u8>>();
+  days.dedup();
+
+  for day in days {
+    let cases = records.iter().filter(|r| r.day == day).collect::<Vec<_>>();
+    let day_count = |status: Status| cases.iter().filter(|r| r.status == status).count();
+    writeln!(
+      output,
+      r#"  <testsuite name="day{day}" tests="{}" failures="{}" errors="{}" skipped="{}" time="{:.6}">"#,
+      cases.len(),
+      day_count(Status::Failed),
+      day_count(Status::Error) + day_count(Status::TimedOut),
+      day_count(Status::Skipped),
+      cases.iter().map(|r| r.total()).sum::<Duration>().as_secs_f64()
+    )
+    .unwrap();
+
+    for case in cases {
+      let name = escape_xml(&format!("part {} ({})", case.part, case.input));
+      let time = case.total().as_secs_f64();
+      let error = escape_xml(case.error.as_deref().unwrap_or_default());
+
+      let body = match case.status {
+        Status::Passed | Status::Solved => None,
+        Status::Failed => Some(format!(
+          r#"<failure message="{}"/>"#,
+          escape_xml(&format!(
+            "expected {}, got {}",
+            case.expected.as_deref().unwrap_or_default(),
+            case.actual.as_deref().unwrap_or_default()
+          ))
+        )),
+        Status::Skipped => Some(format!(r#"<skipped message="{error}"/>"#)),
+        Status::Error | Status::TimedOut => Some(format!(r#"<error message="{error}"/>"#)),
+      };
+
+      match body {
+        Some(body) => writeln!(
+          output,
+          r#"    <testcase classname="day{day}" name="{name}" time="{time:.6}">{body}</testcase>"#
+        ),
+        None => writeln!(
+          output,
+          r#"    <testcase classname="day{day}" name="{name}" time="{time:.6}"/>"#
+        ),
+      }
+      .unwrap();
+    }
+
+    writeln!(output, "  </testsuite>").unwrap();
+  }
+
+  writeln!(output, "</testsuites>").unwrap();
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn records() -> Vec<RunRecord> {
+    vec![
+      RunRecord::new(
+        7,
+        1,
+        "input/day7/test1.txt",
+        Some("3749"),
+        &Ok("3749".to_string()),
+        Duration::from_millis(1),
+        Duration::from_millis(2),
+      ),
+      RunRecord::new(
+        7,
+        2,
+        "input/day7/test1.txt",
+        Some("11387"),
+        &Ok("1 < 2".to_string()),
+        Duration::ZERO,
+        Duration::from_millis(3),
+      ),
+      RunRecord::new(
+        25,
+        2,
+        "input/day25/part1.txt",
+        None,
+        &Err(SolveError::NotApplicable("one part".to_string())),
+        Duration::ZERO,
+        Duration::ZERO,
+      ),
+    ]
+  }
+
+  #[test]
+  fn test_status() {
+    let statuses = records().iter().map(|r| r.status).collect::<Vec<_>>();
+    assert_eq!(
+      statuses,
+      vec![Status::Passed, Status::Failed, Status::Skipped]
+    );
+    assert_eq!(
+      statuses
+        .iter()
+        .map(|status| status.is_ok())
+        .collect::<Vec<_>>(),
+      vec![true, false, true]
+    );
+  }
+
+  #[test]
+  fn test_render_json() {
+    let json: serde_json::Value = serde_json::from_str(&render_json(&records())).unwrap();
+    assert_eq!(json[0]["day"], 7);
+    assert_eq!(json[0]["status"], "passed");
+    assert_eq!(json[0]["parse_ms"], 1.0);
+    assert_eq!(json[0]["elapsed_ms"], 2.0);
+    assert_eq!(json[2]["error"], "not applicable: one part");
+  }
+
+  #[test]
+  fn test_parse_json() {
+    let records = parse_json(&render_json(&records())).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[1].status, Status::Failed);
+    assert_eq!(records[1].actual.as_deref(), Some("1 < 2"));
+    assert_eq!(records[0].parse_elapsed, Duration::from_millis(1));
+  }
+
+  #[test]
+  fn test_render_junit() {
+    let xml = render_junit(&records());
+    assert!(xml.contains(r#"<testsuites tests="3" failures="1" errors="0" skipped="1""#));
+    assert!(xml.contains(r#"<testsuite name="day25" tests="1""#));
+    assert!(xml.contains(r#"<failure message="expected 11387, got 1 &lt; 2"/>"#));
+  }
+
+  #[test]
+  fn test_render_table() {
+    let table = render_table(&records());
+    let lines = table.lines().collect::<Vec<&str>>();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("day  part  input"));
+    assert!(lines[3].contains("not applicable: one part"));
+  }
+}
@@ -0,0 +1,838 @@
+use rayon::prelude::*;
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answers::{self, Answers, Verdict};
+use crate::bench::{self, PartBench};
+use crate::cancel;
+use crate::cli::{
+  AnswerArgs, BenchArgs, CheckArgs, ExtractArgs, FetchArgs, NewArgs, RunArgs, Selection, SubmitArgs,
+};
+use crate::client::{self, Client};
+use crate::error::SolveError;
+use crate::examples::{self, Example, Manifest};
+use crate::extract;
+use crate::fetch::{self, Fetched, Throttle};
+use crate::params::Params;
+use crate::registry::{self, Registry, Solution};
+use crate::report::{self, Format, RunRecord, Status};
+use crate::scaffold;
+use crate::submit;
+use crate::{utils, Solver};
+
+/// The real inputs for each part, `part2.txt` if a day has one, otherwise `part1.txt`.
+fn get_filenames(day_name: &str, selection: &Selection) -> (String, String) {
+  // An explicit input file replaces the real input for both parts
+  if let Some(input) = &selection.input {
+    let input = input.display().to_string();
+    return (input.clone(), input);
+  }
+
+  let day_dir = selection.input_dir.path().join(day_name);
+  let input_1_name = day_dir.join("part1.txt");
+  let input_2_name = day_dir.join("part2.txt");
+  let input_2_name = if input_2_name.exists() {
+    input_2_name
+  } else {
+    input_1_name.clone()
+  };
+
+  (
+    input_1_name.display().to_string(),
+    input_2_name.display().to_string(),
+  )
+}
+
+pub fn select_days(
+  registry: &Registry,
+  selection: &Selection,
+  format: Format,
+) -> Vec<&'static Solution> {
+  for day in selection.named_days() {
+    if registry.get(registry::YEAR, day).is_none() {
+      progress(format, &format!("No solution is registered for day {day}"));
+    }
+  }
+
+  registry
+    .solutions()
+    .filter(|solution| selection.includes_day(solution.day))
+    .collect()
+}
+
+/// Runs part of a solver, turning a panic into an error so it can't take the other days down.
+fn catch_panic<T>(f: impl FnOnce() -> Result<T, SolveError>) -> Result<T, SolveError> {
+  panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+    let message = payload
+      .downcast_ref::<&str>()
+      .map(ToString::to_string)
+      .or_else(|| payload.downcast_ref::<String>().cloned())
+      .unwrap_or_else(|| "unknown panic".to_string());
+    Err(SolveError::Panicked(message))
+  })
+}
+
+/// Prints progress as days are solved, keeping stdout free when it's needed for the report.
+pub fn progress(format: Format, message: &str) {
+  if format.is_machine_readable() {
+    eprintln!("{message}");
+  } else {
+    println!("{message}");
+  }
+}
+
+/// An input once a solver has parsed it, shareable with the thread solving it under a timeout.
+type Parsed = Arc<dyn Any + Send + Sync>;
+
+/// A day's progress, kept until it can be printed without interleaving with other days'.
+pub struct Log {
+  format: Format,
+  lines: Vec<String>,
+}
+
+impl Log {
+  pub fn new(format: Format) -> Self {
+    Log {
+      format,
+      lines: Vec::new(),
+    }
+  }
+
+  fn line(&mut self, message: impl Into<String>) {
+    self.lines.push(message.into());
+  }
+
+  pub fn print(&self) {
+    for line in &self.lines {
+      progress(self.format, line);
+    }
+  }
+}
+
+/// Input files parsed by one solver, so parts sharing a file only parse it once.
+struct ParsedInputs<'a> {
+  solver: &'a dyn Solver,
+  parsed: HashMap<String, Result<Parsed, SolveError>>,
+}
+
+impl<'a> ParsedInputs<'a> {
+  fn new(solver: &'a dyn Solver) -> Self {
+    ParsedInputs {
+      solver,
+      parsed: HashMap::new(),
+    }
+  }
+
+  /// The parsed input, and how long parsing took if it hadn't been parsed already.
+  fn get(&mut self, input_name: &str) -> (&Result<Parsed, SolveError>, Duration) {
+    let mut elapsed = Duration::ZERO;
+    let parsed = self
+      .parsed
+      .entry(input_name.to_string())
+      .or_insert_with(|| {
+        let input = utils::read_input(input_name)
+          .map_err(|err| SolveError::Input(format!("{input_name}: {err}")))?;
+        let start = Instant::now();
+        let parsed = catch_panic(|| self.solver.parse(&input)).map(Parsed::from);
+        elapsed = start.elapsed();
+        parsed
+      });
+    (parsed, elapsed)
+  }
+}
+
+fn solve_input(
+  solution: &Solution,
+  inputs: &mut ParsedInputs,
+  part: u8,
+  input_name: &str,
+  params: &Params,
+  expected: Option<&str>,
+  timeout: Option<Duration>,
+) -> RunRecord {
+  let (parsed, parse_elapsed) = inputs.get(input_name);
+  let start = Instant::now();
+  let answer = match (parsed, timeout) {
+    (Ok(input), None) => catch_panic(|| solution.solver.solve(part, input.as_ref(), params)),
+    (Ok(input), Some(limit)) => {
+      let (solver, input, params) = (solution.solver, Arc::clone(input), params.clone());
+      cancel::run_with_timeout(limit, move || {
+        catch_panic(|| solver.solve(part, input.as_ref(), &params))
+      })
+    }
+    (Err(err), _) => Err(err.clone()),
+  };
+  RunRecord::new(
+    solution.day,
+    part,
+    input_name,
+    expected,
+    &answer,
+    parse_elapsed,
+    start.elapsed(),
+  )
+}
+
+/// The answers recorded for a day, or none if they can't be read.
+fn load_answers(day: &dyn Solver, day_dir: &Path, log: &mut Log) -> Answers {
+  Answers::load(day_dir).unwrap_or_else(|err| {
+    log.line(format!("{} Couldn't read answers: {err}", day.name()));
+    Answers::default()
+  })
+}
+
+/// Solves a real input, failing the part if the answer regressed from the accepted one or was
+/// already rejected.
+fn solve_real_input(
+  solution: &Solution,
+  inputs: &mut ParsedInputs,
+  part: u8,
+  input_name: &str,
+  selection: &Selection,
+  answers: &Answers,
+  day_dir: &Path,
+) -> RunRecord {
+  let key = answers::input_key(day_dir, input_name);
+  let accepted = key.as_deref().and_then(|key| answers.accepted(part, key));
+  let timeout = selection.timeout(solution.day, part);
+  let mut record = solve_input(
+    solution,
+    inputs,
+    part,
+    input_name,
+    &selection.params(),
+    accepted,
+    timeout,
+  );
+
+  if let (Some(key), Some(actual)) = (&key, &record.actual) {
+    if let Verdict::Wrong { rejected, hint } = answers.check(part, key, actual) {
+      let hint = hint.map(|hint| format!(" as {hint}")).unwrap_or_default();
+      record.status = Status::Failed;
+      record.error = Some(if rejected == *actual {
+        format!("{actual} was already rejected{hint}")
+      } else {
+        format!("{rejected} was already rejected{hint}")
+      });
+    }
+  }
+
+  record
+}
+
+fn print_answer(day: &dyn Solver, record: &RunRecord, log: &mut Log) {
+  let part = record.part;
+  let message = match (&record.actual, &record.error) {
+    (Some(answer), _) => format!("{} Part {part}: {answer}", day.name()),
+    (None, error) if record.status == Status::Skipped => {
+      format!(
+        "{} Part {part} Skipped: {}",
+        day.name(),
+        error.as_deref().unwrap_or_default()
+      )
+    }
+    (None, error) => format!(
+      "{} Part {part} Failed: {}",
+      day.name(),
+      error.as_deref().unwrap_or_default()
+    ),
+  };
+  log.line(message);
+
+  if record.status == Status::Failed && record.actual.is_some() {
+    let reason = match (&record.error, &record.expected) {
+      (Some(error), _) => error.clone(),
+      (None, expected) => format!(
+        "doesn't match the accepted answer {}",
+        expected.as_deref().unwrap_or_default()
+      ),
+    };
+    log.line(format!("{} Part {part} Wrong: {reason}", day.name()));
+  }
+
+  log.line(format!(
+    "{:?} (parsing {:?})",
+    record.elapsed, record.parse_elapsed
+  ));
+}
+
+/// Reports how a part did against an example, returning whether it's safe to solve the real input.
+fn check_example(day: &dyn Solver, example: &Example, record: &RunRecord, log: &mut Log) -> bool {
+  let (name, part, label) = (day.name(), record.part, example.label());
+  let error = record.error.as_deref().unwrap_or_default();
+
+  let (message, attempt) = match record.status {
+    Status::Passed | Status::Solved => (format!("{name} Part {part} Test Passed: {label}"), true),
+    Status::Failed => (
+      format!(
+        "{name} Part {part} Test Failed: {label}: expected {}, got {}",
+        record.expected.as_deref().unwrap_or_default(),
+        record.actual.as_deref().unwrap_or_default()
+      ),
+      false,
+    ),
+    Status::Skipped => (
+      format!("{name} Part {part} Test Skipped: {label}: {error}"),
+      true,
+    ),
+    Status::Error | Status::TimedOut => (
+      format!("{name} Part {part} Test Failed: {label}: {error}"),
+      false,
+    ),
+  };
+
+  log.line(message);
+  attempt
+}
+
+pub fn run_day(solution: &Solution, args: &RunArgs, log: &mut Log) -> Vec<RunRecord> {
+  let day = solution.solver;
+  log.line(format!("Running {}", day.name()));
+  let (input_1_name, input_2_name) = get_filenames(&day.name(), &args.selection);
+  let day_dir = args.selection.input_dir.path().join(day.name());
+  let answers = load_answers(day, &day_dir, log);
+
+  let mut inputs = ParsedInputs::new(day);
+  let mut records = Vec::new();
+  for (part, input_name) in [(1, input_1_name), (2, input_2_name)] {
+    if args.selection.includes_part(part) {
+      let record = solve_real_input(
+        solution,
+        &mut inputs,
+        part,
+        &input_name,
+        &args.selection,
+        &answers,
+        &day_dir,
+      );
+      print_answer(day, &record, log);
+      records.push(record);
+    }
+  }
+
+  records
+}
+
+pub fn check_day(solution: &Solution, check_args: &CheckArgs, log: &mut Log) -> Vec<RunRecord> {
+  let args = &check_args.run;
+  let day = solution.solver;
+  log.line(format!("Running {}", day.name()));
+  let (input_1_name, input_2_name) = get_filenames(&day.name(), &args.selection);
+  let day_dir = args.selection.input_dir.path().join(day.name());
+
+  let manifest = match Manifest::load(&day_dir, day) {
+    Ok(manifest) => manifest,
+    Err(err) => {
+      let answer = Err(SolveError::Input(err));
+      return [1, 2]
+        .into_iter()
+        .filter(|part| args.selection.includes_part(*part))
+        .map(|part| {
+          let record = RunRecord::new(
+            solution.day,
+            part,
+            &day_dir.join(examples::MANIFEST).display().to_string(),
+            None,
+            &answer,
+            Duration::ZERO,
+            Duration::ZERO,
+          );
+          print_answer(day, &record, log);
+          record
+        })
+        .collect();
+    }
+  };
+
+  let answers = load_answers(day, &day_dir, log);
+  let mut inputs = ParsedInputs::new(day);
+  let mut records = Vec::new();
+  for (part, input_name) in [(1, input_1_name), (2, input_2_name)] {
+    if !args.selection.includes_part(part) {
+      continue;
+    }
+
+    let mut attempt = true;
+    let mut examples = manifest.for_part(part).peekable();
+    if examples.peek().is_none() {
+      log.line(format!("{} Part {part} has no examples", day.name()));
+    }
+
+    for example in examples {
+      let example_name = day_dir.join(&example.file).display().to_string();
+      let record = solve_input(
+        solution,
+        &mut inputs,
+        part,
+        &example_name,
+        &example.params,
+        example.expected(part),
+        args.selection.timeout(solution.day, part),
+      );
+      attempt &= check_example(day, example, &record, log);
+      records.push(record);
+    }
+
+    if !attempt {
+      break;
+    }
+
+    if check_args.examples_only {
+      continue;
+    }
+
+    let record = solve_real_input(
+      solution,
+      &mut inputs,
+      part,
+      &input_name,
+      &args.selection,
+      &answers,
+      &day_dir,
+    );
+    print_answer(day, &record, log);
+    records.push(record);
+  }
+
+  records
+}
+
+/// Runs `f` for each day in parallel on `--jobs` threads, printing each day's log whole. Logs are
+/// printed in day order as soon as every earlier day's has been, or as each day finishes with
+/// `--stream`.
+fn run_in_order<F>(solutions: &[&'static Solution], args: &RunArgs, f: F) -> Vec<RunRecord>
+where
+  F: Fn(&Solution, &mut Log) -> Vec<RunRecord> + Sync,
+{
+  let pool = match rayon::ThreadPoolBuilder::new()
+    .num_threads(args.jobs().unwrap_or_default())
+    .build()
+  {
+    Ok(pool) => pool,
+    Err(err) => {
+      progress(
+        args.format,
+        &format!("Couldn't start the thread pool: {err}"),
+      );
+      return Vec::new();
+    }
+  };
+
+  let (sender, receiver) = mpsc::channel::<(usize, Log)>();
+  thread::scope(|scope| {
+    scope.spawn(|| {
+      let mut pending = BTreeMap::new();
+      let mut next = 0;
+      for (idx, log) in receiver {
+        if args.stream {
+          log.print();
+          continue;
+        }
+
+        pending.insert(idx, log);
+        while let Some(log) = pending.remove(&next) {
+          log.print();
+          next += 1;
+        }
+      }
+    });
+
+    let records = pool.install(|| {
+      solutions
+        .par_iter()
+        .enumerate()
+        .flat_map_iter(|(idx, solution)| {
+          let mut log = Log::new(args.format);
+          let records = f(solution, &mut log);
+          sender.send((idx, log)).expect("The printer stopped early");
+          records
+        })
+        .collect()
+    });
+    // The printer finishes once every sender is gone
+    drop(sender);
+    records
+  })
+}
+
+/// Checks each selected day against its examples and then its real input, days in parallel.
+pub fn check_days(registry: &Registry, args: &CheckArgs) -> Vec<RunRecord> {
+  let solutions = select_days(registry, &args.run.selection, args.run.format);
+  if !args.examples_only {
+    offer_fetch(&solutions, &args.run);
+  }
+  run_in_order(&solutions, &args.run, |solution, log| {
+    check_day(solution, args, log)
+  })
+}
+
+/// Solves each selected day's real input, days in parallel.
+pub fn run_days(registry: &Registry, args: &RunArgs) -> Vec<RunRecord> {
+  let solutions = select_days(registry, &args.selection, args.format);
+  offer_fetch(&solutions, args);
+  run_in_order(&solutions, args, |solution, log| {
+    run_day(solution, args, log)
+  })
+}
+
+pub fn print_report(format: Format, records: &[RunRecord]) {
+  match format {
+    Format::Table => println!("\n{}", report::render(format, records)),
+    Format::Json | Format::Junit => print!("{}", report::render(format, records)),
+  }
+}
+
+pub fn bench_day(
+  solution: &Solution,
+  args: &BenchArgs,
+  options: &bench::Options,
+) -> Vec<PartBench> {
+  let day = solution.solver;
+  let (input_1_name, input_2_name) = get_filenames(&day.name(), &args.selection);
+  let params = args.selection.params();
+
+  let mut results = Vec::new();
+  for (part, input_name) in [(1, input_1_name), (2, input_2_name)] {
+    if !args.selection.includes_part(part) {
+      continue;
+    }
+
+    println!("Benchmarking {} Part {part}", day.name());
+    let input = match utils::read_input(&input_name) {
+      Ok(input) => input,
+      Err(err) => {
+        println!("{} Part {part} Failed: {input_name}: {err}", day.name());
+        continue;
+      }
+    };
+    let result = bench::sample(solution.day, part, options, || {
+      let start = Instant::now();
+      let parsed = catch_panic(|| day.parse(&input))?;
+      let parse = start.elapsed();
+
+      let start = Instant::now();
+      catch_panic(|| day.solve(part, parsed.as_ref(), &params))?;
+      Ok((parse, start.elapsed()))
+    });
+
+    match result {
+      Ok(result) => results.push(result),
+      Err(err) if err.is_skip() => println!("{} Part {part} Skipped: {err}", day.name()),
+      Err(err) => println!("{} Part {part} Failed: {err}", day.name()),
+    }
+  }
+
+  results
+}
+
+pub fn bench_days(registry: &Registry, args: &BenchArgs) {
+  let options = bench::Options {
+    warmup: args.warmup,
+    runs: args.runs,
+    budget: args.budget,
+  };
+
+  // Benchmarks run one day at a time so they don't compete for cores
+  let results = select_days(registry, &args.selection, Format::Table)
+    .iter()
+    .flat_map(|solution| bench_day(solution, args, &options))
+    .collect::<Vec<PartBench>>();
+
+  println!("\n{}", bench::render(&results));
+
+  if let Some(path) = &args.baseline {
+    match bench::load_baseline(path) {
+      Ok(baseline) => {
+        let comparisons = bench::compare(&baseline, &results, args.threshold);
+        print!("{}", bench::render_comparison(&comparisons));
+        let regressions = comparisons.iter().filter(|c| c.regressed).count();
+        if regressions > 0 {
+          println!(
+            "{regressions} part(s) regressed by more than {}%",
+            args.threshold
+          );
+        }
+      }
+      Err(err) => eprintln!("Couldn't read baseline {}: {err}", path.display()),
+    }
+  }
+
+  if let Some(path) = &args.save_baseline {
+    if let Err(err) = bench::save_baseline(path, &results) {
+      eprintln!("Couldn't save baseline {}: {err}", path.display());
+    }
+  }
+}
+
+pub fn list_days(registry: &Registry) {
+  for solution in registry.solutions() {
+    println!("{} {}", solution.year, solution.solver.name());
+  }
+
+  for year in registry.years() {
+    let missing = registry.missing(year);
+    if !missing.is_empty() {
+      let days = missing
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+      println!("{year} is missing days {}", days.join(", "));
+    }
+  }
+}
+
+pub fn new_day(registry: &Registry, args: &NewArgs) {
+  if registry.get(args.year, args.day).is_some() {
+    println!("{} day {} already has a solution", args.year, args.day);
+    return;
+  }
+
+  let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+  match scaffold::create(&src_dir, &args.input_dir.path(), args.year, args.day) {
+    Ok(created) => {
+      for path in created {
+        println!("Created {}", path.display());
+      }
+      println!(
+        "Fill in src/day{0}.rs, then run `cargo run -- check --day {0}`",
+        args.day
+      );
+    }
+    Err(err) => println!("Couldn't create day {}: {err}", args.day),
+  }
+}
+
+fn fetch_days(
+  client: &Client,
+  throttle: &Throttle,
+  input_dir: &Path,
+  solutions: &[&Solution],
+  format: Format,
+) {
+  for solution in solutions {
+    let message = match fetch::fetch(client, throttle, input_dir, solution.year, solution.day) {
+      Ok(Fetched::Downloaded(path)) => format!("Downloaded {}", path.display()),
+      Ok(Fetched::Cached(path)) => format!("Already have {}", path.display()),
+      Err(err) => format!("Couldn't fetch day {}: {err}", solution.day),
+    };
+    progress(format, &message);
+  }
+}
+
+/// Downloads the inputs for the selected days that don't have one yet.
+pub fn fetch_inputs(registry: &Registry, args: &FetchArgs) {
+  let solutions = registry
+    .solutions()
+    .filter(|solution| solution.year == args.year)
+    .filter(|solution| args.day.is_none_or(|day| solution.day == day))
+    .collect::<Vec<_>>();
+  if solutions.is_empty() {
+    println!("No solutions to fetch inputs for");
+    return;
+  }
+
+  let client = match Client::from_env(args.url.as_deref()) {
+    Ok(client) => client,
+    Err(err) => {
+      println!("Couldn't fetch: {err}");
+      return;
+    }
+  };
+  let input_dir = args.input_dir.path();
+  let throttle = Throttle::new(&input_dir, Duration::from_secs(args.throttle));
+  fetch_days(&client, &throttle, &input_dir, &solutions, Format::Table);
+}
+
+/// Offers to download the real inputs that are missing for the selected days, if there's someone
+/// at the terminal to ask and a session to download them with.
+fn offer_fetch(solutions: &[&'static Solution], args: &RunArgs) {
+  let missing = solutions
+    .iter()
+    .copied()
+    .filter(|solution| {
+      let (input_1_name, _) = get_filenames(&solution.solver.name(), &args.selection);
+      args.selection.input.is_none() && !fetch::is_cached(Path::new(&input_1_name))
+    })
+    .collect::<Vec<_>>();
+  if missing.is_empty() {
+    return;
+  }
+
+  let names = missing
+    .iter()
+    .map(|solution| solution.solver.name())
+    .collect::<Vec<_>>()
+    .join(", ");
+  let client = Client::from_env(None);
+  let (Ok(client), true) = (client, io::stdin().is_terminal()) else {
+    progress(
+      args.format,
+      &format!(
+        "No input for {names}, set ${} and run `cargo run -- fetch` to download it",
+        client::SESSION_VAR
+      ),
+    );
+    return;
+  };
+
+  eprint!("No input for {names}, download it now? [y/N] ");
+  let mut reply = String::new();
+  if io::stdin().read_line(&mut reply).is_err() || !reply.trim().eq_ignore_ascii_case("y") {
+    return;
+  }
+
+  let input_dir = args.selection.input_dir.path();
+  let throttle = Throttle::new(&input_dir, fetch::DEFAULT_THROTTLE);
+  fetch_days(&client, &throttle, &input_dir, &missing, args.format);
+}
+
+/// Lists the blocks and emphasized values on a saved puzzle page, then writes out the examples.
+pub fn extract_examples(args: &ExtractArgs) {
+  let html = match fs::read_to_string(&args.page) {
+    Ok(html) => html,
+    Err(err) => {
+      println!("Couldn't read {}: {err}", args.page.display());
+      return;
+    }
+  };
+  let page = extract::parse(&html);
+
+  for (idx, block) in page.blocks.iter().enumerate() {
+    println!("Block {} (part {}):", idx + 1, block.part);
+    for line in block.text.lines() {
+      println!("  {line}");
+    }
+    let emphasized = page
+      .emphasized
+      .iter()
+      .filter(|emphasis| emphasis.block == Some(idx))
+      .map(|emphasis| format!("{} (part {})", emphasis.text, emphasis.part))
+      .collect::<Vec<_>>();
+    if !emphasized.is_empty() {
+      println!("Emphasized after it: {}", emphasized.join(", "));
+    }
+  }
+
+  for (block, part1, part2) in page.examples() {
+    println!(
+      "Example: block {}, part 1 {}, part 2 {}",
+      block + 1,
+      part1.as_deref().unwrap_or("-"),
+      part2.as_deref().unwrap_or("-")
+    );
+  }
+  if args.dry_run {
+    return;
+  }
+
+  let day_dir = args.input_dir.path().join(format!("day{}", args.day));
+  match extract::write(&page, &day_dir, args.force) {
+    Ok(written) => {
+      for path in written {
+        println!("Wrote {}", path.display());
+      }
+    }
+    Err(err) => println!("Couldn't write the examples: {err}"),
+  }
+}
+
+/// Solves the part's real input and submits the answer, recording what the site said about it.
+pub fn submit_answer(registry: &Registry, args: &SubmitArgs) {
+  let Some(solution) = registry.get(args.year, args.day) else {
+    println!("{} day {} doesn't have a solution", args.year, args.day);
+    return;
+  };
+
+  let run_args = RunArgs {
+    selection: Selection {
+      day: Some(args.day),
+      part: Some(args.part),
+      input_dir: args.input_dir.clone(),
+      ..Selection::default()
+    },
+    ..RunArgs::default()
+  };
+  let mut log = Log::new(run_args.format);
+  let mut records = run_day(solution, &run_args, &mut log);
+  log.print();
+  let Some(record) = records.pop() else {
+    return;
+  };
+  let Some(answer) = &record.actual else {
+    println!("Nothing to submit");
+    return;
+  };
+
+  let day_dir = args.input_dir.path().join(solution.solver.name());
+  let Some(input) = answers::input_key(&day_dir, &record.input) else {
+    println!("{} isn't in {}", record.input, day_dir.display());
+    return;
+  };
+  let mut answers = match Answers::load(&day_dir) {
+    Ok(answers) => answers,
+    Err(err) => {
+      println!("Couldn't read answers: {err}");
+      return;
+    }
+  };
+  let client = match Client::from_env(args.url.as_deref()) {
+    Ok(client) => client,
+    Err(err) => {
+      println!("Couldn't submit: {err}");
+      return;
+    }
+  };
+
+  let puzzle = (args.year, args.day, args.part);
+  match submit::submit(&client, &mut answers, puzzle, &input, answer) {
+    Ok(outcome) => {
+      println!("Submitted {answer}: {outcome}");
+      if let Err(err) = answers.save(&day_dir) {
+        println!("Couldn't save answers: {err}");
+      }
+    }
+    Err(err) => println!("Didn't submit {answer}: {err}"),
+  }
+}
+
+pub fn record_answer(args: &AnswerArgs) {
+  let day_dir = args.input_dir.path().join(format!("day{}", args.day));
+  let input = args.input.clone().unwrap_or_else(|| {
+    if args.part == 2 && day_dir.join("part2.txt").exists() {
+      "part2.txt".to_string()
+    } else {
+      "part1.txt".to_string()
+    }
+  });
+
+  let mut answers = match Answers::load(&day_dir) {
+    Ok(answers) => answers,
+    Err(err) => {
+      println!("Couldn't read answers: {err}");
+      return;
+    }
+  };
+
+  match (&args.accept, &args.reject) {
+    (Some(answer), _) => answers.accept(args.part, &input, answer),
+    (None, Some(answer)) => answers.reject(args.part, &input, answer, args.hint),
+    (None, None) => unreachable!("clap requires --accept or --reject"),
+  }
+
+  match answers.save(&day_dir) {
+    Ok(()) => println!(
+      "Recorded day{} Part {} ({input}) in {}",
+      args.day,
+      args.part,
+      day_dir.join(answers::ANSWERS).display()
+    ),
+    Err(err) => println!("Couldn't save answers: {err}"),
+  }
+}
@@ -0,0 +1,88 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The template every new day starts from. It isn't compiled itself, since `build.rs` only picks
+/// up `dayX.rs` files with a number for `X`.
+const TEMPLATE: &str = include_str!("dayN.rs");
+
+/// The inputs a new day starts with, empty until the puzzle is opened.
+const INPUT_FILES: [&str; 2] = ["test1.txt", "part1.txt"];
+
+/// The template, filled in for `day` of `year`.
+pub fn render(year: u16, day: u8) -> String {
+  TEMPLATE
+    .replace(
+      "Solution::new(2024, N, &DayN {})",
+      &format!("Solution::new({year}, {day}, &Day{day} {{}})"),
+    )
+    .replace("DayN", &format!("Day{day}"))
+    .replace("dayN", &format!("day{day}"))
+}
+
+/// Writes the module for `day` into `src_dir` and creates its input directory in `input_dir`,
+/// returning the paths it created. `build.rs` registers the module on the next build.
+///
+/// Fails without touching anything if the module already exists. Input files that already exist
+/// are left alone.
+pub fn create(src_dir: &Path, input_dir: &Path, year: u16, day: u8) -> io::Result<Vec<PathBuf>> {
+  let module = src_dir.join(format!("day{day}.rs"));
+  let mut file = OpenOptions::new()
+    .write(true)
+    .create_new(true)
+    .open(&module)
+    .map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", module.display())))?;
+  file.write_all(render(year, day).as_bytes())?;
+
+  let mut created = vec![module];
+
+  let day_dir = input_dir.join(format!("day{day}"));
+  fs::create_dir_all(&day_dir)?;
+  for name in INPUT_FILES {
+    let path = day_dir.join(name);
+    if !path.exists() {
+      fs::write(&path, "")?;
+      created.push(path);
+    }
+  }
+
+  Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::temp_dir::TempDir;
+
+  #[test]
+  fn test_render() {
+    let module = render(2025, 26);
+    assert!(module.contains("pub const SOLUTION: Solution = Solution::new(2025, 26, &Day26 {});"));
+    assert!(module.contains("impl AOCDay for Day26 {"));
+    assert!(module.contains("read_file(\"input/day26/test1.txt\")"));
+    assert!(!module.contains("DayN"));
+    assert!(!module.contains("dayN"));
+  }
+
+  #[test]
+  fn test_create() {
+    let root = TempDir::new("scaffold");
+    let (src_dir, input_dir) = (root.join("src"), root.join("input"));
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::create_dir_all(input_dir.join("day26")).unwrap();
+    fs::write(input_dir.join("day26/part1.txt"), "1 2 3").unwrap();
+
+    let created = create(&src_dir, &input_dir, 2024, 26).unwrap();
+    assert_eq!(
+      created,
+      vec![src_dir.join("day26.rs"), input_dir.join("day26/test1.txt")]
+    );
+    assert_eq!(
+      fs::read_to_string(input_dir.join("day26/part1.txt")).unwrap(),
+      "1 2 3"
+    );
+
+    let err = create(&src_dir, &input_dir, 2024, 26).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+  }
+}
@@ -0,0 +1,267 @@
+use regex::Regex;
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::answers::{Answers, Hint, Verdict};
+use crate::client::{Client, ClientError};
+
+/// What the site said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+  Correct,
+  Wrong(Option<Hint>),
+  /// An answer was submitted too recently. Nothing was checked.
+  RateLimited(Option<Duration>),
+  /// The part was already solved, or part 1 hasn't been yet.
+  WrongLevel,
+  /// The response didn't look like any of the above.
+  Unrecognised(String),
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Outcome::Correct => write!(f, "correct"),
+      Outcome::Wrong(Some(hint)) => write!(f, "wrong, {hint}"),
+      Outcome::Wrong(None) => write!(f, "wrong"),
+      Outcome::RateLimited(Some(wait)) => write!(f, "rate limited, try again in {wait:?}"),
+      Outcome::RateLimited(None) => write!(f, "rate limited"),
+      Outcome::WrongLevel => write!(f, "not the right level, was it already solved?"),
+      Outcome::Unrecognised(text) => write!(f, "unrecognised response: {text}"),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitError {
+  Client(ClientError),
+  /// The answer was already accepted, so there's nothing to submit.
+  AlreadyAccepted,
+  /// A different answer was already accepted.
+  Regressed {
+    accepted: String,
+  },
+  /// The answer is known to be wrong, so submitting it would only earn a timeout.
+  KnownWrong {
+    rejected: String,
+    hint: Option<Hint>,
+  },
+}
+
+impl fmt::Display for SubmitError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      SubmitError::Client(err) => write!(f, "{err}"),
+      SubmitError::AlreadyAccepted => write!(f, "the answer was already accepted"),
+      SubmitError::Regressed { accepted } => {
+        write!(f, "a different answer, {accepted}, was already accepted")
+      }
+      SubmitError::KnownWrong {
+        rejected,
+        hint: Some(hint),
+      } => write!(f, "{rejected} was already rejected as {hint}"),
+      SubmitError::KnownWrong {
+        rejected,
+        hint: None,
+      } => {
+        write!(f, "{rejected} was already rejected")
+      }
+    }
+  }
+}
+
+impl From<ClientError> for SubmitError {
+  fn from(err: ClientError) -> Self {
+    SubmitError::Client(err)
+  }
+}
+
+/// The text of the response's `<article>`, without tags.
+fn article_text(body: &str) -> String {
+  static ARTICLE: OnceLock<Regex> = OnceLock::new();
+  static TAG: OnceLock<Regex> = OnceLock::new();
+  let article = ARTICLE.get_or_init(|| Regex::new(r"(?s)<article>(.*?)</article>").unwrap());
+  let tag = TAG.get_or_init(|| Regex::new(r"<[^>]*>").unwrap());
+
+  let html = article
+    .captures(body)
+    .map_or(body, |captures| captures.get(1).unwrap().as_str());
+  tag
+    .replace_all(html, "")
+    .split_whitespace()
+    .collect::<Vec<&str>>()
+    .join(" ")
+}
+
+/// How long "You have 1m 5s left to wait" says to wait.
+fn parse_wait(text: &str) -> Option<Duration> {
+  static WAIT: OnceLock<Regex> = OnceLock::new();
+  let wait = WAIT
+    .get_or_init(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap())
+    .captures(text)?;
+
+  let minutes = wait
+    .get(1)
+    .map_or(Ok(0), |m| m.as_str().parse::<u64>())
+    .ok()?;
+  let seconds = wait[2].parse::<u64>().ok()?;
+  Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+pub fn parse_response(body: &str) -> Outcome {
+  let text = article_text(body);
+
+  if text.contains("That's the right answer") {
+    Outcome::Correct
+  } else if text.contains("That's not the right answer") {
+    let hint = if text.contains("too high") {
+      Some(Hint::TooHigh)
+    } else if text.contains("too low") {
+      Some(Hint::TooLow)
+    } else {
+      None
+    };
+    Outcome::Wrong(hint)
+  } else if text.contains("You gave an answer too recently") {
+    Outcome::RateLimited(parse_wait(&text))
+  } else if text.contains("You don't seem to be solving the right level") {
+    Outcome::WrongLevel
+  } else {
+    Outcome::Unrecognised(text)
+  }
+}
+
+/// Records what the site said about `answer` in `answers`. Outcomes that don't say whether the
+/// answer was right aren't recorded.
+pub fn record(answers: &mut Answers, part: u8, input: &str, answer: &str, outcome: &Outcome) {
+  match outcome {
+    Outcome::Correct => answers.accept(part, input, answer),
+    Outcome::Wrong(hint) => answers.reject(part, input, answer, *hint),
+    Outcome::RateLimited(_) | Outcome::WrongLevel | Outcome::Unrecognised(_) => (),
+  }
+}
+
+/// Submits `answer` for `part` of `input`, unless `answers` already says what the site would, and
+/// records the outcome in `answers`.
+pub fn submit(
+  client: &Client,
+  answers: &mut Answers,
+  (year, day, part): (u16, u8, u8),
+  input: &str,
+  answer: &str,
+) -> Result<Outcome, SubmitError> {
+  match answers.check(part, input, answer) {
+    Verdict::Correct => return Err(SubmitError::AlreadyAccepted),
+    Verdict::Regressed { accepted } => return Err(SubmitError::Regressed { accepted }),
+    Verdict::Wrong { rejected, hint } => return Err(SubmitError::KnownWrong { rejected, hint }),
+    Verdict::Unknown | Verdict::Untried => (),
+  }
+
+  let body = client.submit_answer(year, day, part, answer)?;
+  let outcome = parse_response(&body);
+  record(answers, part, input, answer, &outcome);
+  Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::mock_server::MockServer;
+
+  const CORRECT: &str = "<html><main><article><p>That's the right answer!  You are <em>one gold star</em> closer to finding the Chief Historian.</p></article></main></html>";
+  const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again.</p></article></main>";
+  const RATE_LIMITED: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 12s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>";
+
+  #[test]
+  fn test_parse_response() {
+    assert_eq!(parse_response(CORRECT), Outcome::Correct);
+    assert_eq!(
+      parse_response(TOO_HIGH),
+      Outcome::Wrong(Some(Hint::TooHigh))
+    );
+    assert_eq!(
+      parse_response("<article><p>That's not the right answer.</p></article>"),
+      Outcome::Wrong(None)
+    );
+    assert_eq!(
+      parse_response(RATE_LIMITED),
+      Outcome::RateLimited(Some(Duration::from_secs(252)))
+    );
+    assert_eq!(
+      parse_response(
+        "<article><p>You have 9s left to wait. You gave an answer too recently</p></article>"
+      ),
+      Outcome::RateLimited(Some(Duration::from_secs(9)))
+    );
+    assert_eq!(
+      parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"),
+      Outcome::WrongLevel
+    );
+    assert_eq!(
+      parse_response("<p>Puzzle inputs differ by user.</p>"),
+      Outcome::Unrecognised("Puzzle inputs differ by user.".to_string())
+    );
+  }
+
+  #[test]
+  fn test_submit() {
+    let server = MockServer::start(vec![(200, TOO_HIGH), (200, RATE_LIMITED), (200, CORRECT)]);
+    let client = Client::new(&server.url, "abc123");
+    let mut answers = Answers::default();
+    let puzzle = (2024, 1, 2);
+
+    let submit =
+      |answers: &mut Answers, answer: &str| submit(&client, answers, puzzle, "part2.txt", answer);
+
+    assert_eq!(
+      submit(&mut answers, "900"),
+      Ok(Outcome::Wrong(Some(Hint::TooHigh)))
+    );
+    // Known to be wrong, so it isn't sent
+    assert_eq!(
+      submit(&mut answers, "901"),
+      Err(SubmitError::KnownWrong {
+        rejected: "900".to_string(),
+        hint: Some(Hint::TooHigh)
+      })
+    );
+    assert_eq!(
+      submit(&mut answers, "800"),
+      Ok(Outcome::RateLimited(Some(Duration::from_secs(252))))
+    );
+    assert_eq!(submit(&mut answers, "800"), Ok(Outcome::Correct));
+    assert_eq!(
+      submit(&mut answers, "800"),
+      Err(SubmitError::AlreadyAccepted)
+    );
+    assert_eq!(answers.accepted(2, "part2.txt"), Some("800"));
+
+    let requests = server.finish();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2024/day/1/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    assert_eq!(requests[0].body, "level=2&answer=900");
+  }
+
+  #[test]
+  fn test_submit_error_status() {
+    let server = MockServer::start(vec![(500, "oops")]);
+    let client = Client::new(&server.url, "abc123");
+
+    assert_eq!(
+      submit(
+        &client,
+        &mut Answers::default(),
+        (2024, 1, 1),
+        "part1.txt",
+        "1"
+      ),
+      Err(SubmitError::Client(ClientError::Status {
+        code: 500,
+        body: "oops".to_string()
+      }))
+    );
+  }
+}
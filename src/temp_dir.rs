@@ -0,0 +1,34 @@
+//! Scratch directories for tests that touch the filesystem.
+
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::{env, process};
+
+/// An empty directory under the system's temp directory, removed again when it's dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+  /// Each test should use its own `name`, since tests run in parallel.
+  pub fn new(name: &str) -> Self {
+    let path = env::temp_dir().join(format!("advent2024-{name}-{}", process::id()));
+    // Left behind by a run that was killed before cleaning up
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    TempDir(path)
+  }
+}
+
+impl Deref for TempDir {
+  type Target = Path;
+
+  fn deref(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempDir {
+  fn drop(&mut self) {
+    let _ = fs::remove_dir_all(&self.0);
+  }
+}
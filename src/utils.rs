@@ -1,66 +1,50 @@
-use std::fmt;
+use std::io::{self, Read};
 use std::string::ToString;
-
-pub fn read_file(file: &str) -> Vec<String> {
-  let contents = std::fs::read_to_string(file).expect("Could not read file");
-  let lines: Vec<String> = contents.lines().map(ToString::to_string).collect();
-  lines
-}
-
-pub fn i32_to_usize(value: i32) -> Option<usize> {
-  value.try_into().ok()
-}
-pub fn i32_to_usize_x(value: i32) -> usize {
-  let v = value.try_into();
-
-  if v.ok().is_none() {
-    println!("Failed to convert i32 to usize: {value}");
+use std::sync::OnceLock;
+
+mod convert;
+mod direction;
+mod grid;
+pub mod math;
+pub mod parse;
+mod point;
+
+pub use convert::{ConversionError, Convert};
+pub use direction::{Direction, Direction8};
+pub use grid::Grid;
+pub use point::{ParsePointError, Point};
+
+/// Where input is read from when `-` is given instead of a path.
+pub const STDIN: &str = "-";
+
+/// Splits puzzle input into the lines the solvers work on.
+pub fn lines(input: &str) -> Vec<String> {
+  input.lines().map(ToString::to_string).collect()
+}
+
+/// Reads input from the file at `source`, or from stdin if it's `-`.
+pub fn read_input(source: &str) -> io::Result<Vec<String>> {
+  if source == STDIN {
+    // Stdin can only be read once, so keep it for every part that asks for it
+    static CONTENTS: OnceLock<Result<String, String>> = OnceLock::new();
+    let contents = CONTENTS.get_or_init(|| {
+      let mut contents = String::new();
+      io::stdin()
+        .read_to_string(&mut contents)
+        .map(|_| contents)
+        .map_err(|err| err.to_string())
+    });
+    return contents
+      .as_deref()
+      .map(lines)
+      .map_err(|err| io::Error::other(err.clone()));
   }
 
-  v.expect("Failed to convert i32 to usize")
+  std::fs::read_to_string(source).map(|contents| lines(&contents))
 }
 
-pub fn usize_to_i32(value: usize) -> Option<i32> {
-  value.try_into().ok()
-}
-pub fn usize_to_i32_x(value: usize) -> i32 {
-  value.try_into().expect("Failed to convert usize to i32")
-}
-
-pub fn usize_to_u32_x(value: usize) -> u32 {
-  value.try_into().expect("Failed to convert usize to u32")
-}
-
-pub fn u32_to_i32(value: u32) -> Option<i32> {
-  value.try_into().ok()
-}
-
-pub fn isize_to_usize_x(value: isize) -> usize {
-  value.try_into().expect("Failed to convert isize to usize")
-}
-pub fn u32_to_usize_x(value: u32) -> usize {
-  value.try_into().expect("Failed to convert isize to usize")
-}
-
-pub fn u64_to_u32_x(value: u64) -> u32 {
-  value.try_into().expect("Failed to convert u64 to u32")
-}
-
-// Mark - Points
-
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-pub struct Point {
-  pub x: i32,
-  pub y: i32,
-}
-
-impl fmt::Display for Point {
-  // This trait requires `fmt` with this exact signature.
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    // Write strictly the first element into the supplied output
-    // stream: `f`. Returns `fmt::Result` which indicates whether the
-    // operation succeeded or failed. Note that `write!` uses syntax which
-    // is very similar to `println!`.
-    write!(f, "{},{}", self.x, self.y)
-  }
+/// Reads an input file that is known to exist, for tests.
+#[cfg(test)]
+pub fn read_file(file: &str) -> Vec<String> {
+  read_input(file).expect("Could not read file")
 }
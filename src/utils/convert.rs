@@ -0,0 +1,82 @@
+use std::any::type_name;
+use std::fmt;
+
+/// A number that doesn't fit in the type it was converted to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionError {
+  pub value: String,
+  pub from: &'static str,
+  pub to: &'static str,
+}
+
+impl fmt::Display for ConversionError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} doesn't fit in a {}, converting from {}",
+      self.value, self.to, self.from
+    )
+  }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Checked conversions between any two integer types, e.g. `count.convert::<i32>()`.
+pub trait Convert: Copy + fmt::Display + Sized {
+  fn convert<T: TryFrom<Self>>(self) -> Result<T, ConversionError> {
+    T::try_from(self).map_err(|_| ConversionError {
+      value: self.to_string(),
+      from: type_name::<Self>(),
+      to: type_name::<T>(),
+    })
+  }
+
+  /// Converts a value that's known to fit, panicking at the caller if it doesn't.
+  #[track_caller]
+  fn convert_x<T: TryFrom<Self>>(self) -> T {
+    match self.convert() {
+      Ok(value) => value,
+      Err(err) => panic!("{err}"),
+    }
+  }
+}
+
+macro_rules! impl_convert {
+  ($($int:ty),*) => {
+    $(impl Convert for $int {})*
+  };
+}
+
+impl_convert!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_convert() {
+    assert_eq!(5_u32.convert::<usize>(), Ok(5));
+    assert_eq!((-1_i64).convert::<i8>(), Ok(-1));
+    assert_eq!(3_000_000_000_u64.convert_x::<i64>(), 3_000_000_000);
+
+    let err = (-1_i32).convert::<usize>().unwrap_err();
+    assert_eq!(
+      err,
+      ConversionError {
+        value: "-1".to_string(),
+        from: "i32",
+        to: "usize"
+      }
+    );
+    assert_eq!(
+      err.to_string(),
+      "-1 doesn't fit in a usize, converting from i32"
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "300 doesn't fit in a u8, converting from u32")]
+  fn test_convert_x() {
+    300_u32.convert_x::<u8>();
+  }
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+use strum_macros::EnumIter;
+
+use super::Point;
+
+/// One of the four ways to move on a grid, with up being towards `y = 0`. Iterates clockwise from
+/// up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
+pub enum Direction {
+  Up,
+  Right,
+  Down,
+  Left,
+}
+
+/// A `Direction`, or one of the diagonals between them. Iterates clockwise from up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, EnumIter)]
+pub enum Direction8 {
+  Up,
+  UpRight,
+  Right,
+  DownRight,
+  Down,
+  DownLeft,
+  Left,
+  UpLeft,
+}
+
+impl Direction {
+  /// The direction an arrow points, from `^`, `v`, `<` or `>`.
+  pub fn from_char(char: char) -> Option<Self> {
+    match char {
+      '^' => Some(Direction::Up),
+      '>' => Some(Direction::Right),
+      'v' => Some(Direction::Down),
+      '<' => Some(Direction::Left),
+      _ => None,
+    }
+  }
+
+  #[must_use]
+  pub fn turn_right(self) -> Self {
+    match self {
+      Direction::Up => Direction::Right,
+      Direction::Right => Direction::Down,
+      Direction::Down => Direction::Left,
+      Direction::Left => Direction::Up,
+    }
+  }
+
+  #[must_use]
+  pub fn turn_left(self) -> Self {
+    self.reverse().turn_right()
+  }
+
+  #[must_use]
+  pub fn reverse(self) -> Self {
+    self.turn_right().turn_right()
+  }
+
+  pub fn is_vertical(self) -> bool {
+    matches!(self, Direction::Up | Direction::Down)
+  }
+
+  /// How far one step moves along `x` and `y`.
+  pub fn offset(self) -> (i32, i32) {
+    Direction8::from(self).offset()
+  }
+
+  /// The point one step from `point`, which may be outside the grid.
+  pub fn step(self, point: Point) -> Point {
+    Direction8::from(self).step(point)
+  }
+
+  /// The point one step from `point`, if it's inside a `width` by `height` grid.
+  pub fn step_within(self, point: Point, width: usize, height: usize) -> Option<Point> {
+    Direction8::from(self).step_within(point, width, height)
+  }
+}
+
+impl Direction8 {
+  #[must_use]
+  pub fn turn_right(self) -> Self {
+    match self {
+      Direction8::Up => Direction8::UpRight,
+      Direction8::UpRight => Direction8::Right,
+      Direction8::Right => Direction8::DownRight,
+      Direction8::DownRight => Direction8::Down,
+      Direction8::Down => Direction8::DownLeft,
+      Direction8::DownLeft => Direction8::Left,
+      Direction8::Left => Direction8::UpLeft,
+      Direction8::UpLeft => Direction8::Up,
+    }
+  }
+
+  #[must_use]
+  pub fn turn_left(self) -> Self {
+    self.reverse().turn_right().turn_right().turn_right()
+  }
+
+  #[must_use]
+  pub fn reverse(self) -> Self {
+    self.turn_right().turn_right().turn_right().turn_right()
+  }
+
+  pub fn is_diagonal(self) -> bool {
+    let (dx, dy) = self.offset();
+    dx != 0 && dy != 0
+  }
+
+  /// How far one step moves along `x` and `y`.
+  pub fn offset(self) -> (i32, i32) {
+    match self {
+      Direction8::Up => (0, -1),
+      Direction8::UpRight => (1, -1),
+      Direction8::Right => (1, 0),
+      Direction8::DownRight => (1, 1),
+      Direction8::Down => (0, 1),
+      Direction8::DownLeft => (-1, 1),
+      Direction8::Left => (-1, 0),
+      Direction8::UpLeft => (-1, -1),
+    }
+  }
+
+  /// The point one step from `point`, which may be outside the grid.
+  pub fn step(self, point: Point) -> Point {
+    let (dx, dy) = self.offset();
+    point + Point::new(dx, dy)
+  }
+
+  /// The point one step from `point`, if it's inside a `width` by `height` grid.
+  pub fn step_within(self, point: Point, width: usize, height: usize) -> Option<Point> {
+    let next = self.step(point);
+    let x = usize::try_from(next.x).ok()?;
+    let y = usize::try_from(next.y).ok()?;
+    (x < width && y < height).then_some(next)
+  }
+}
+
+impl From<Direction> for Direction8 {
+  fn from(direction: Direction) -> Self {
+    match direction {
+      Direction::Up => Direction8::Up,
+      Direction::Right => Direction8::Right,
+      Direction::Down => Direction8::Down,
+      Direction::Left => Direction8::Left,
+    }
+  }
+}
+
+impl fmt::Display for Direction {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let arrow = match self {
+      Direction::Up => '^',
+      Direction::Right => '>',
+      Direction::Down => 'v',
+      Direction::Left => '<',
+    };
+    write!(f, "{arrow}")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use strum::IntoEnumIterator;
+
+  #[test]
+  fn test_turns() {
+    for direction in Direction::iter() {
+      assert_eq!(direction.turn_right().turn_left(), direction);
+      assert_eq!(direction.reverse().reverse(), direction);
+      assert_eq!(
+        Direction::from_char(direction.to_string().chars().next().unwrap()),
+        Some(direction)
+      );
+    }
+    assert_eq!(Direction::Up.turn_left(), Direction::Left);
+    assert_eq!(Direction::Right.reverse(), Direction::Left);
+
+    for direction in Direction8::iter() {
+      assert_eq!(direction.turn_left().turn_right(), direction);
+      let (dx, dy) = direction.offset();
+      assert_eq!(direction.reverse().offset(), (-dx, -dy));
+    }
+    assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+    assert_eq!(Direction8::iter().filter(|d| d.is_diagonal()).count(), 4);
+  }
+
+  #[test]
+  fn test_step() {
+    let corner = Point { x: 0, y: 0 };
+    assert_eq!(Direction::Down.step(corner), Point { x: 0, y: 1 });
+    assert_eq!(Direction::Up.step(corner), Point { x: 0, y: -1 });
+    assert_eq!(Direction::Up.step_within(corner, 3, 3), None);
+    assert_eq!(
+      Direction8::DownRight.step_within(corner, 3, 3),
+      Some(Point { x: 1, y: 1 })
+    );
+    assert_eq!(
+      Direction::Right.step_within(Point { x: 2, y: 0 }, 3, 3),
+      None
+    );
+  }
+}
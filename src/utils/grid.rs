@@ -0,0 +1,233 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use strum::IntoEnumIterator;
+
+use super::{Direction, Direction8, Point};
+use crate::error::SolveError;
+
+/// A rectangular grid of cells, indexed by `Point` with `x` as the column and `y` as the row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  width: usize,
+  height: usize,
+  cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+  pub fn new(width: usize, height: usize, fill: T) -> Self
+  where
+    T: Clone,
+  {
+    Grid {
+      width,
+      height,
+      cells: vec![fill; width * height],
+    }
+  }
+
+  /// Builds a grid from lines of input, turning each character into a cell with `cell`. Trailing
+  /// blank lines are ignored.
+  pub fn parse(
+    lines: &[String],
+    mut cell: impl FnMut(char) -> Option<T>,
+  ) -> Result<Self, SolveError> {
+    let lines = match lines.iter().rposition(|line| !line.is_empty()) {
+      Some(last) => &lines[..=last],
+      None => &[],
+    };
+    let width = lines.first().map_or(0, |line| line.chars().count());
+
+    let mut cells = Vec::with_capacity(width * lines.len());
+    for (line_idx, line) in lines.iter().enumerate() {
+      let row_len = line.chars().count();
+      if row_len != width {
+        return Err(SolveError::parse(
+          line_idx,
+          format!("expected {width} columns, found {row_len}"),
+        ));
+      }
+
+      for (col_idx, char) in line.chars().enumerate() {
+        let value = cell(char).ok_or_else(|| {
+          SolveError::parse(line_idx, format!("unexpected character '{char}'")).at_column(col_idx)
+        })?;
+        cells.push(value);
+      }
+    }
+
+    Ok(Grid {
+      width,
+      height: lines.len(),
+      cells,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn contains(&self, point: Point) -> bool {
+    self.offset(point).is_some()
+  }
+
+  pub fn get(&self, point: Point) -> Option<&T> {
+    self.offset(point).map(|offset| &self.cells[offset])
+  }
+
+  pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+    self.offset(point).map(|offset| &mut self.cells[offset])
+  }
+
+  /// Every point in the grid, row by row.
+  pub fn points(&self) -> impl Iterator<Item = Point> {
+    let (width, height) = (self.width, self.height);
+    (0..height).flat_map(move |y| (0..width).map(move |x| point_at(x, y)))
+  }
+
+  /// Every cell in the grid with its point, row by row.
+  pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+    self.points().zip(self.cells.iter())
+  }
+
+  /// The points above, right of, below and left of `point` that are in the grid, in that order.
+  pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> {
+    let (width, height) = (self.width, self.height);
+    Direction::iter().filter_map(move |direction| direction.step_within(point, width, height))
+  }
+
+  /// The points around `point`, diagonals included, that are in the grid, clockwise from above.
+  pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> {
+    let (width, height) = (self.width, self.height);
+    Direction8::iter().filter_map(move |direction| direction.step_within(point, width, height))
+  }
+
+  /// The first point, row by row, whose cell is `value`.
+  pub fn find(&self, value: &T) -> Option<Point>
+  where
+    T: PartialEq,
+  {
+    self
+      .iter()
+      .find_map(|(point, cell)| (cell == value).then_some(point))
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    self.cells.chunks(self.width.max(1))
+  }
+
+  pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+    assert!(x < self.width, "Column {x} is outside the grid");
+    self.cells.iter().skip(x).step_by(self.width)
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(|x| self.column(x))
+  }
+
+  fn offset(&self, point: Point) -> Option<usize> {
+    let x = usize::try_from(point.x).ok()?;
+    let y = usize::try_from(point.y).ok()?;
+    (x < self.width && y < self.height).then_some(y * self.width + x)
+  }
+}
+
+fn point_at(x: usize, y: usize) -> Point {
+  Point {
+    x: x.try_into().expect("Grid is too wide for a Point"),
+    y: y.try_into().expect("Grid is too tall for a Point"),
+  }
+}
+
+impl<T> Index<Point> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, point: Point) -> &T {
+    self
+      .get(point)
+      .unwrap_or_else(|| panic!("{point} is outside the grid"))
+  }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+  fn index_mut(&mut self, point: Point) -> &mut T {
+    self
+      .get_mut(point)
+      .unwrap_or_else(|| panic!("{point} is outside the grid"))
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for row in self.rows() {
+      for cell in row {
+        write!(f, "{cell}")?;
+      }
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::lines;
+
+  fn grid() -> Grid<char> {
+    Grid::parse(&lines("#..\n.S.\n..E\n\n"), Some).unwrap()
+  }
+
+  #[test]
+  fn test_parse() {
+    let grid = grid();
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert_eq!(grid[Point { x: 1, y: 1 }], 'S');
+    assert_eq!(grid.find(&'E'), Some(Point { x: 2, y: 2 }));
+    assert_eq!(grid.to_string(), "#..\n.S.\n..E\n");
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    let digits = |char: char| char.to_digit(10);
+    assert_eq!(
+      Grid::parse(&lines("12\n3x"), digits),
+      Err(SolveError::parse(1, "unexpected character 'x'").at_column(1))
+    );
+    assert_eq!(
+      Grid::parse(&lines("12\n345"), digits),
+      Err(SolveError::parse(1, "expected 2 columns, found 3"))
+    );
+  }
+
+  #[test]
+  fn test_bounds() {
+    let grid = grid();
+    assert_eq!(grid.get(Point { x: -1, y: 0 }), None);
+    assert_eq!(grid.get(Point { x: 3, y: 0 }), None);
+    assert!(grid.contains(Point { x: 2, y: 0 }));
+  }
+
+  #[test]
+  fn test_neighbours() {
+    let grid = grid();
+    let corner = Point { x: 0, y: 0 };
+    assert_eq!(
+      grid.neighbours(corner).collect::<Vec<Point>>(),
+      vec![Point { x: 1, y: 0 }, Point { x: 0, y: 1 }]
+    );
+    assert_eq!(grid.neighbours8(corner).count(), 3);
+    assert_eq!(grid.neighbours8(Point { x: 1, y: 1 }).count(), 8);
+  }
+
+  #[test]
+  fn test_rows_and_columns() {
+    let grid = grid();
+    assert_eq!(grid.rows().nth(1), Some(&['.', 'S', '.'][..]));
+    assert_eq!(grid.column(2).collect::<String>(), "..E");
+    assert_eq!(grid.columns().count(), 3);
+  }
+}
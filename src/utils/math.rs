@@ -0,0 +1,341 @@
+//! Number theory for puzzles that can be solved by construction rather than by search. Everything
+//! works on `i64`, and reports overflow and division by zero rather than wrapping or panicking.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+  /// An intermediate value didn't fit in an `i64`.
+  Overflow,
+  /// A divisor or modulus was 0.
+  DivisionByZero,
+  /// There's no integer solution.
+  NoSolution,
+  /// There are infinitely many solutions, where one was expected.
+  NotUnique,
+}
+
+impl fmt::Display for MathError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      MathError::Overflow => write!(f, "overflowed an i64"),
+      MathError::DivisionByZero => write!(f, "divided by zero"),
+      MathError::NoSolution => write!(f, "no integer solution"),
+      MathError::NotUnique => write!(f, "infinitely many solutions"),
+    }
+  }
+}
+
+impl std::error::Error for MathError {}
+
+pub fn checked_add(a: i64, b: i64) -> Result<i64, MathError> {
+  a.checked_add(b).ok_or(MathError::Overflow)
+}
+
+pub fn checked_sub(a: i64, b: i64) -> Result<i64, MathError> {
+  a.checked_sub(b).ok_or(MathError::Overflow)
+}
+
+pub fn checked_mul(a: i64, b: i64) -> Result<i64, MathError> {
+  a.checked_mul(b).ok_or(MathError::Overflow)
+}
+
+fn checked_neg(a: i64) -> Result<i64, MathError> {
+  a.checked_neg().ok_or(MathError::Overflow)
+}
+
+/// `a / b`, which overflows for `i64::MIN / -1`.
+fn checked_div(a: i64, b: i64) -> Result<i64, MathError> {
+  if b == 0 {
+    return Err(MathError::DivisionByZero);
+  }
+  a.checked_div(b).ok_or(MathError::Overflow)
+}
+
+/// `a / b` rounded towards negative infinity.
+pub fn div_floor(a: i64, b: i64) -> Result<i64, MathError> {
+  let quotient = checked_div(a, b)?;
+  if a % b != 0 && (a < 0) != (b < 0) {
+    checked_sub(quotient, 1)
+  } else {
+    Ok(quotient)
+  }
+}
+
+/// `a / b` rounded towards positive infinity.
+pub fn div_ceil(a: i64, b: i64) -> Result<i64, MathError> {
+  let quotient = checked_div(a, b)?;
+  if a % b != 0 && (a < 0) == (b < 0) {
+    checked_add(quotient, 1)
+  } else {
+    Ok(quotient)
+  }
+}
+
+/// The greatest common divisor, which is never negative. `gcd(0, 0)` is 0.
+pub fn gcd(a: i64, b: i64) -> Result<i64, MathError> {
+  let (mut a, mut b) = (a, b);
+  while b != 0 {
+    // `i64::MIN % -1` overflows, though the remainder is 0
+    (a, b) = (b, if b == -1 { 0 } else { a % b });
+  }
+  a.checked_abs().ok_or(MathError::Overflow)
+}
+
+/// The least common multiple, which is never negative. It's 0 if either is 0.
+pub fn lcm(a: i64, b: i64) -> Result<i64, MathError> {
+  if a == 0 || b == 0 {
+    return Ok(0);
+  }
+  let a_part = checked_div(a, gcd(a, b)?)?;
+  checked_mul(
+    a_part.checked_abs().ok_or(MathError::Overflow)?,
+    b.checked_abs().ok_or(MathError::Overflow)?,
+  )
+}
+
+/// `(g, x, y)` where `g` is `gcd(a, b)` and `a * x + b * y = g`.
+#[allow(clippy::many_single_char_names)]
+pub fn extended_gcd(a: i64, b: i64) -> Result<(i64, i64, i64), MathError> {
+  let (mut old_r, mut r) = (a, b);
+  let (mut old_x, mut x) = (1, 0);
+  let (mut old_y, mut y) = (0, 1);
+  while r != 0 {
+    let quotient = checked_div(old_r, r)?;
+    (old_r, r) = (r, checked_sub(old_r, checked_mul(quotient, r)?)?);
+    (old_x, x) = (x, checked_sub(old_x, checked_mul(quotient, x)?)?);
+    (old_y, y) = (y, checked_sub(old_y, checked_mul(quotient, y)?)?);
+  }
+
+  if old_r < 0 {
+    Ok((
+      checked_neg(old_r)?,
+      checked_neg(old_x)?,
+      checked_neg(old_y)?,
+    ))
+  } else {
+    Ok((old_r, old_x, old_y))
+  }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, which exists when `a` and `modulus`
+/// are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Result<i64, MathError> {
+  if modulus == 0 {
+    return Err(MathError::DivisionByZero);
+  }
+  let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus)?;
+  if g == 1 {
+    Ok(x.rem_euclid(modulus))
+  } else {
+    Err(MathError::NoSolution)
+  }
+}
+
+/// The `(residue, modulus)` that every `x` with `x ≡ residue_i (mod modulus_i)` for each of
+/// `congruences` shares, by the Chinese Remainder Theorem. The moduli needn't be coprime, as long
+/// as the congruences agree.
+pub fn crt(congruences: &[(i64, i64)]) -> Result<(i64, i64), MathError> {
+  congruences.iter().try_fold(
+    (0, 1),
+    |(residue, modulus), &(next_residue, next_modulus)| {
+      if next_modulus == 0 {
+        return Err(MathError::DivisionByZero);
+      }
+
+      // x = residue + modulus * k, which needs
+      // modulus * k ≡ next_residue - residue (mod next_modulus)
+      let (g, inverse, _) = extended_gcd(modulus, next_modulus)?;
+      let difference = checked_sub(next_residue, residue)?;
+      if difference % g != 0 {
+        return Err(MathError::NoSolution);
+      }
+
+      let step = next_modulus / g;
+      let k = i128::from(difference / g) * i128::from(inverse) % i128::from(step);
+      let combined = lcm(modulus, next_modulus)?;
+      let x = (i128::from(residue) + i128::from(modulus) * k).rem_euclid(i128::from(combined));
+      Ok((i64::try_from(x).map_err(|_| MathError::Overflow)?, combined))
+    },
+  )
+}
+
+/// Every integer solution to `a * x + b * y = c`, as `(x + k * step_x, y + k * step_y)` for any
+/// integer `k`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinearSolutions {
+  pub x: i64,
+  pub y: i64,
+  pub step_x: i64,
+  pub step_y: i64,
+}
+
+/// Solves `a * x + b * y = c` over the integers, with extended Euclid.
+#[allow(clippy::many_single_char_names)]
+pub fn solve_diophantine(a: i64, b: i64, c: i64) -> Result<LinearSolutions, MathError> {
+  let (g, x, y) = extended_gcd(a, b)?;
+  if g == 0 {
+    return if c == 0 {
+      Err(MathError::NotUnique)
+    } else {
+      Err(MathError::NoSolution)
+    };
+  }
+  if c % g != 0 {
+    return Err(MathError::NoSolution);
+  }
+
+  let scale = c / g;
+  Ok(LinearSolutions {
+    x: checked_mul(x, scale)?,
+    y: checked_mul(y, scale)?,
+    step_x: b / g,
+    step_y: checked_neg(a / g)?,
+  })
+}
+
+/// The integer `(x, y)` that solves
+///
+/// ```text
+/// matrix[0][0] * x + matrix[0][1] * y = rhs[0]
+/// matrix[1][0] * x + matrix[1][1] * y = rhs[1]
+/// ```
+///
+/// by Cramer's rule. Errors with `NotUnique` when the equations are multiples of each other, and
+/// `NoSolution` when they contradict or only have a fractional solution.
+#[allow(clippy::many_single_char_names)]
+pub fn solve_2x2(matrix: [[i64; 2]; 2], rhs: [i64; 2]) -> Result<(i64, i64), MathError> {
+  let [[a, b], [c, d]] = matrix;
+  let [e, f] = rhs;
+
+  let determinant = checked_sub(checked_mul(a, d)?, checked_mul(b, c)?)?;
+  let x_numerator = checked_sub(checked_mul(e, d)?, checked_mul(b, f)?)?;
+  let y_numerator = checked_sub(checked_mul(a, f)?, checked_mul(e, c)?)?;
+
+  if determinant == 0 {
+    // The equations agree when the augmented matrix has the same rank as `matrix`. The numerators
+    // are its other minors, so them being 0 only rules out rank 2. With every coefficient 0,
+    // `rhs` has to be 0 too.
+    let no_coefficients = matrix.iter().flatten().all(|&coefficient| coefficient == 0);
+    return if x_numerator != 0 || y_numerator != 0 || (no_coefficients && rhs != [0, 0]) {
+      Err(MathError::NoSolution)
+    } else {
+      Err(MathError::NotUnique)
+    };
+  }
+  if x_numerator % determinant != 0 || y_numerator % determinant != 0 {
+    return Err(MathError::NoSolution);
+  }
+
+  Ok((
+    checked_div(x_numerator, determinant)?,
+    checked_div(y_numerator, determinant)?,
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_gcd_lcm() {
+    assert_eq!(gcd(12, 18), Ok(6));
+    assert_eq!(gcd(-12, 18), Ok(6));
+    assert_eq!(gcd(7, 0), Ok(7));
+    assert_eq!(gcd(0, 0), Ok(0));
+    assert_eq!(gcd(i64::MIN, -1), Ok(1));
+    assert_eq!(gcd(i64::MIN, 0), Err(MathError::Overflow));
+    assert_eq!(lcm(4, 6), Ok(12));
+    assert_eq!(lcm(-4, 6), Ok(12));
+    assert_eq!(lcm(0, 6), Ok(0));
+    assert_eq!(lcm(i64::MAX, i64::MAX - 1), Err(MathError::Overflow));
+    assert_eq!(lcm(i64::MIN, 1), Err(MathError::Overflow));
+  }
+
+  #[test]
+  fn test_div() {
+    assert_eq!(div_floor(7, 2), Ok(3));
+    assert_eq!(div_floor(-7, 2), Ok(-4));
+    assert_eq!(div_floor(-6, 2), Ok(-3));
+    assert_eq!(div_ceil(7, 2), Ok(4));
+    assert_eq!(div_ceil(-7, 2), Ok(-3));
+    assert_eq!(div_ceil(7, -2), Ok(-3));
+    assert_eq!(div_floor(7, 0), Err(MathError::DivisionByZero));
+    assert_eq!(div_ceil(7, 0), Err(MathError::DivisionByZero));
+    assert_eq!(div_floor(i64::MIN, -1), Err(MathError::Overflow));
+    assert_eq!(div_ceil(i64::MIN, -1), Err(MathError::Overflow));
+  }
+
+  #[test]
+  fn test_extended_gcd() {
+    for (a, b) in [(240, 46), (-240, 46), (17, 5), (0, 5), (5, 0)] {
+      let (g, x, y) = extended_gcd(a, b).unwrap();
+      assert_eq!(Ok(g), gcd(a, b));
+      assert_eq!(a * x + b * y, g);
+    }
+    assert_eq!(extended_gcd(i64::MIN, 0), Err(MathError::Overflow));
+  }
+
+  #[test]
+  fn test_mod_inverse() {
+    assert_eq!(mod_inverse(3, 11), Ok(4));
+    assert_eq!(mod_inverse(-3, 11), Ok(7));
+    assert_eq!(mod_inverse(101, 103), Ok(51));
+    assert_eq!(mod_inverse(4, 8), Err(MathError::NoSolution));
+    assert_eq!(mod_inverse(4, 0), Err(MathError::DivisionByZero));
+  }
+
+  #[test]
+  fn test_crt() {
+    assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+    assert_eq!(crt(&[(27, 101), (75, 103)]), Ok((8006, 10403)));
+    // Moduli that share a factor
+    assert_eq!(crt(&[(2, 4), (4, 6)]), Ok((10, 12)));
+    assert_eq!(crt(&[(1, 4), (2, 6)]), Err(MathError::NoSolution));
+    assert_eq!(crt(&[]), Ok((0, 1)));
+    assert_eq!(crt(&[(2, 3), (0, 0)]), Err(MathError::DivisionByZero));
+  }
+
+  #[test]
+  fn test_solve_diophantine() {
+    let solutions = solve_diophantine(94, 22, 8400).unwrap();
+    for k in -2..=2 {
+      let x = solutions.x + k * solutions.step_x;
+      let y = solutions.y + k * solutions.step_y;
+      assert_eq!(94 * x + 22 * y, 8400);
+    }
+    assert_eq!(solve_diophantine(4, 6, 5), Err(MathError::NoSolution));
+    assert_eq!(solve_diophantine(0, 0, 0), Err(MathError::NotUnique));
+    assert_eq!(solve_diophantine(i64::MIN, 0, 0), Err(MathError::Overflow));
+    assert_eq!(solve_diophantine(i64::MIN, 1, 0), Err(MathError::Overflow));
+  }
+
+  #[test]
+  fn test_solve_2x2() {
+    assert_eq!(solve_2x2([[94, 22], [34, 67]], [8400, 5400]), Ok((80, 40)));
+    assert_eq!(
+      solve_2x2([[26, 67], [66, 21]], [12748, 12176]),
+      Err(MathError::NoSolution)
+    );
+    assert_eq!(
+      solve_2x2([[1, 2], [1, 2]], [4, 4]),
+      Err(MathError::NotUnique)
+    );
+    assert_eq!(
+      solve_2x2([[1, 2], [1, 2]], [4, 5]),
+      Err(MathError::NoSolution)
+    );
+    assert_eq!(
+      solve_2x2([[0, 0], [0, 0]], [1, 1]),
+      Err(MathError::NoSolution)
+    );
+    assert_eq!(
+      solve_2x2([[0, 0], [0, 0]], [0, 0]),
+      Err(MathError::NotUnique)
+    );
+    assert_eq!(
+      solve_2x2([[i64::MAX, 2], [3, i64::MAX]], [1, 1]),
+      Err(MathError::Overflow)
+    );
+  }
+}
@@ -0,0 +1,146 @@
+//! Parsers for the shapes puzzle input usually comes in. Each takes the 0-based index of the line
+//! it's looking at, so its errors point at the right place in the input.
+
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use super::Grid;
+use crate::error::SolveError;
+
+/// Parses `text`, all of which should be a `T`.
+pub fn value<T>(line_idx: usize, text: &str) -> Result<T, SolveError>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  text
+    .trim()
+    .parse()
+    .map_err(|err| SolveError::parse(line_idx, format!("couldn't parse `{}`: {err}", text.trim())))
+}
+
+/// Every integer in `line`, with its sign, ignoring whatever is around them. `Button A: X+94, Y-34`
+/// gives `[94, -34]`.
+pub fn ints<T>(line_idx: usize, line: &str) -> Result<Vec<T>, SolveError>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  static INT: OnceLock<Regex> = OnceLock::new();
+  let int = INT.get_or_init(|| Regex::new(r"-?\d+").unwrap());
+
+  int
+    .find_iter(line)
+    .map(|found| value(line_idx, found.as_str()).map_err(|err| err.at_column(found.start())))
+    .collect()
+}
+
+/// The values in `line` between each `delimiter`, e.g. `75,47,61` with `,`.
+pub fn list<T>(line_idx: usize, line: &str, delimiter: &str) -> Result<Vec<T>, SolveError>
+where
+  T: FromStr,
+  T::Err: fmt::Display,
+{
+  let mut col_idx = 0;
+  line
+    .split(delimiter)
+    .map(|item| {
+      // `value` trims the item, so the error points past any leading whitespace
+      let leading = item.len() - item.trim_start().len();
+      let result = value(line_idx, item).map_err(|err| err.at_column(col_idx + leading));
+      col_idx += item.len() + delimiter.len();
+      result
+    })
+    .collect()
+}
+
+/// Splits a `key: value` line into its key and value.
+pub fn key_value(line_idx: usize, line: &str) -> Result<(&str, &str), SolveError> {
+  line
+    .split_once(':')
+    .map(|(key, value)| (key.trim(), value.trim()))
+    .ok_or_else(|| SolveError::parse(line_idx, "expected `key: value`"))
+}
+
+/// The groups of lines between blank lines, each with the index of its first line. Extra blank
+/// lines don't make empty groups.
+pub fn sections(input: &[String]) -> impl Iterator<Item = (usize, &[String])> {
+  let mut first_line_idx = 0;
+  input.split(String::is_empty).filter_map(move |section| {
+    let line_idx = first_line_idx;
+    first_line_idx += section.len() + 1;
+    (!section.is_empty()).then_some((line_idx, section))
+  })
+}
+
+/// A grid of the characters in `lines`, which start at `first_line_idx` in the input.
+pub fn char_grid(first_line_idx: usize, lines: &[String]) -> Result<Grid<char>, SolveError> {
+  Grid::parse(lines, Some).map_err(|err| err.below(first_line_idx))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::{lines, Point};
+
+  #[test]
+  fn test_ints() {
+    assert_eq!(ints::<i64>(0, "Button A: X+94, Y-34"), Ok(vec![94, -34]));
+    assert_eq!(ints::<u8>(0, "no numbers"), Ok(vec![]));
+    assert_eq!(
+      ints::<u8>(2, "p=1,300"),
+      Err(
+        SolveError::parse(
+          2,
+          "couldn't parse `300`: number too large to fit in target type"
+        )
+        .at_column(4)
+      )
+    );
+  }
+
+  #[test]
+  fn test_list() {
+    assert_eq!(list(0, "75,47,61", ","), Ok(vec![75, 47, 61]));
+    assert_eq!(
+      list::<String>(0, "r, wr, b", ","),
+      Ok(vec!["r".to_string(), "wr".to_string(), "b".to_string()])
+    );
+    assert_eq!(
+      list::<u32>(3, "1 2 x", " "),
+      Err(SolveError::parse(3, "couldn't parse `x`: invalid digit found in string").at_column(4))
+    );
+    assert_eq!(
+      list::<u32>(0, "1, 2, x", ","),
+      Err(SolveError::parse(0, "couldn't parse `x`: invalid digit found in string").at_column(6))
+    );
+  }
+
+  #[test]
+  fn test_key_value() {
+    assert_eq!(key_value(0, "190: 10 19"), Ok(("190", "10 19")));
+    assert!(key_value(0, "190 10 19").is_err());
+  }
+
+  #[test]
+  fn test_sections() {
+    let input = lines("a\nb\n\nc\n\n\nd\n");
+    assert_eq!(
+      sections(&input).collect::<Vec<_>>(),
+      vec![(0, &input[0..2]), (3, &input[3..4]), (6, &input[6..7])]
+    );
+  }
+
+  #[test]
+  fn test_char_grid() {
+    let input = lines("#.\n..\n\n#.\n.");
+    let (first_line_idx, section) = sections(&input).nth(1).unwrap();
+    assert_eq!(
+      char_grid(first_line_idx, section),
+      Err(SolveError::parse(4, "expected 2 columns, found 1"))
+    );
+    assert_eq!(char_grid(0, &input[..2]).unwrap()[Point::new(0, 0)], '#');
+  }
+}
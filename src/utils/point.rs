@@ -0,0 +1,234 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+use super::{Direction, Direction8};
+
+/// A position, or an offset between two positions. Grids use `Point<i32>`, with `x` as the column
+/// and `y` as the row, so that stepping off the edge can be checked for rather than overflowing.
+#[derive(Debug, Default, Copy, Clone, Eq, Hash, PartialEq)]
+pub struct Point<T = i32> {
+  pub x: T,
+  pub y: T,
+}
+
+impl<T> Point<T> {
+  pub const fn new(x: T, y: T) -> Self {
+    Point { x, y }
+  }
+}
+
+impl<T: Copy + Ord + Sub<Output = T>> Point<T> {
+  /// How far apart the points are moving only along `x` and `y`.
+  pub fn manhattan_distance(self, other: Self) -> T
+  where
+    T: Add<Output = T>,
+  {
+    abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+  }
+
+  /// How far apart the points are when diagonal moves are allowed.
+  pub fn chebyshev_distance(self, other: Self) -> T {
+    abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+  }
+}
+
+impl Point {
+  /// The points above, right, below and left of this one, which may be outside the grid.
+  pub fn neighbours(self) -> impl Iterator<Item = Point> {
+    Direction::iter().map(move |direction| direction.step(self))
+  }
+
+  /// The points around this one, clockwise from above, diagonals included.
+  pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+    Direction8::iter().map(move |direction| direction.step(self))
+  }
+}
+
+/// Works for unsigned coordinates too, where `a - b` alone could underflow.
+fn abs_diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+  a.max(b) - a.min(b)
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+  type Output = Self;
+
+  fn add(self, other: Self) -> Self {
+    Point {
+      x: self.x + other.x,
+      y: self.y + other.y,
+    }
+  }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+  type Output = Self;
+
+  fn sub(self, other: Self) -> Self {
+    Point {
+      x: self.x - other.x,
+      y: self.y - other.y,
+    }
+  }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+  type Output = Self;
+
+  fn mul(self, scale: T) -> Self {
+    Point {
+      x: self.x * scale,
+      y: self.y * scale,
+    }
+  }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+  type Output = Self;
+
+  fn neg(self) -> Self {
+    Point {
+      x: -self.x,
+      y: -self.y,
+    }
+  }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+  fn add_assign(&mut self, other: Self) {
+    self.x += other.x;
+    self.y += other.y;
+  }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+  fn sub_assign(&mut self, other: Self) {
+    self.x -= other.x;
+    self.y -= other.y;
+  }
+}
+
+/// Points are ordered the way a grid is read, row by row.
+impl<T: Ord> Ord for Point<T> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.y.cmp(&other.y).then_with(|| self.x.cmp(&other.x))
+  }
+}
+
+impl<T: Ord> PartialOrd for Point<T> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<T: fmt::Display> fmt::Display for Point<T> {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{},{}", self.x, self.y)
+  }
+}
+
+/// Why a string isn't an `x,y` point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePointError(String);
+
+impl fmt::Display for ParsePointError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl std::error::Error for ParsePointError {}
+
+/// Parses `x,y`, as the puzzles write points, allowing spaces around either number.
+impl<T: FromStr> FromStr for Point<T> {
+  type Err = ParsePointError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (x, y) = s
+      .split_once(',')
+      .ok_or_else(|| ParsePointError(format!("expected `x,y`, found `{s}`")))?;
+    let coordinate = |value: &str| {
+      value
+        .trim()
+        .parse()
+        .map_err(|_| ParsePointError(format!("`{}` isn't a valid coordinate", value.trim())))
+    };
+    Ok(Point {
+      x: coordinate(x)?,
+      y: coordinate(y)?,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_arithmetic() {
+    let a = Point::new(3, -2);
+    let b = Point::new(1, 4);
+    assert_eq!(a + b, Point::new(4, 2));
+    assert_eq!(a - b, Point::new(2, -6));
+    assert_eq!(a * 3, Point::new(9, -6));
+    assert_eq!(-a, Point::new(-3, 2));
+
+    let mut c = a;
+    c += b;
+    c -= Point::new(4, 0);
+    assert_eq!(c, Point::new(0, 2));
+  }
+
+  #[test]
+  fn test_distances() {
+    let a = Point::new(3, -2);
+    let b = Point::new(1, 4);
+    assert_eq!(a.manhattan_distance(b), 8);
+    assert_eq!(a.chebyshev_distance(b), 6);
+
+    let c: Point<usize> = Point::new(1, 5);
+    assert_eq!(c.manhattan_distance(Point::new(4, 2)), 6);
+  }
+
+  #[test]
+  fn test_ordering() {
+    let mut points = vec![Point::new(2, 0), Point::new(0, 1), Point::new(1, 0)];
+    points.sort();
+    assert_eq!(
+      points,
+      vec![Point::new(1, 0), Point::new(2, 0), Point::new(0, 1)]
+    );
+  }
+
+  #[test]
+  fn test_parse() {
+    assert_eq!("6,1".parse(), Ok(Point::new(6, 1)));
+    assert_eq!(" 6, -1".parse(), Ok(Point::new(6, -1)));
+    assert_eq!("6,1".parse(), Ok(Point::<u64>::new(6, 1)));
+    assert!("6".parse::<Point>().is_err());
+    assert_eq!(
+      "6,a".parse::<Point>().unwrap_err().to_string(),
+      "`a` isn't a valid coordinate"
+    );
+  }
+
+  #[test]
+  fn test_neighbours() {
+    let point = Point::new(0, 0);
+    assert_eq!(
+      point.neighbours().collect::<Vec<Point>>(),
+      vec![
+        Point::new(0, -1),
+        Point::new(1, 0),
+        Point::new(0, 1),
+        Point::new(-1, 0)
+      ]
+    );
+    assert_eq!(point.neighbours8().count(), 8);
+    assert!(point
+      .neighbours8()
+      .all(|neighbour| neighbour.chebyshev_distance(point) == 1));
+  }
+}
@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::cli::WatchArgs;
+use crate::report::{self, RunRecord, Status};
+
+/// When each watched file was last modified.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The modification times of `files`, and of every file directly inside `dirs`. Missing files and
+/// directories are left out, so creating or deleting one counts as a change.
+pub fn snapshot(files: &[PathBuf], dirs: &[PathBuf]) -> Snapshot {
+  let dir_files = dirs
+    .iter()
+    .filter_map(|dir| fs::read_dir(dir).ok())
+    .flatten()
+    .filter_map(|entry| Some(entry.ok()?.path()));
+
+  files
+    .iter()
+    .cloned()
+    .chain(dir_files)
+    .filter_map(|path| {
+      let modified = fs::metadata(&path).ok()?.modified().ok()?;
+      Some((path, modified))
+    })
+    .collect()
+}
+
+/// Identifies a part and input, so the same check can be found across runs.
+type Key = (u8, String, usize);
+
+fn keyed(records: &[RunRecord]) -> Vec<(Key, &RunRecord)> {
+  let mut keyed: Vec<(Key, &RunRecord)> = Vec::new();
+  for record in records {
+    // The same example can be listed more than once with different params
+    let nth = keyed
+      .iter()
+      .filter(|((part, input, _), _)| *part == record.part && *input == record.input)
+      .count();
+    keyed.push(((record.part, record.input.clone(), nth), record));
+  }
+  keyed
+}
+
+fn describe(record: &RunRecord) -> String {
+  let error = record.error.as_deref().unwrap_or_default();
+  let actual = record.actual.as_deref().unwrap_or_default();
+  match record.status {
+    Status::Passed => "PASS".to_string(),
+    Status::Failed => format!(
+      "FAIL: expected {}, got {actual}",
+      record.expected.as_deref().unwrap_or_default()
+    ),
+    Status::Solved => actual.to_string(),
+    Status::Skipped => format!("SKIP: {error}"),
+    Status::Error => format!("ERROR: {error}"),
+    Status::TimedOut => format!("TIMEOUT: {error}"),
+  }
+}
+
+/// One line per part and input, noting anything that changed since the `previous` run.
+pub fn render_changes(previous: &[RunRecord], current: &[RunRecord]) -> String {
+  let previous = keyed(previous)
+    .into_iter()
+    .collect::<BTreeMap<Key, &RunRecord>>();
+
+  let mut output = String::new();
+  for (key, record) in keyed(current) {
+    let (part, input, _) = &key;
+    let name = Path::new(input)
+      .file_name()
+      .map_or(input.clone(), |name| name.to_string_lossy().to_string());
+    let now = describe(record);
+    let change = match previous.get(&key).map(|record| describe(record)) {
+      Some(before) if before != now => format!(" (was {before})"),
+      Some(_) => String::new(),
+      None if previous.is_empty() => String::new(),
+      None => " (new)".to_string(),
+    };
+    writeln!(output, "Part {part} {name}: {now}{change}").unwrap();
+  }
+  output
+}
+
+/// `cargo run -- check` for the watched day, with the JSON report on stdout.
+fn check_command(args: &WatchArgs) -> Command {
+  let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+  let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+  let mut command = Command::new(cargo);
+  command
+    .arg("run")
+    .arg("--quiet")
+    .arg("--manifest-path")
+    .arg(manifest)
+    .args(["--", "check", "--format", "json", "--day"])
+    .arg(args.day.to_string())
+    .arg("--input-dir")
+    .arg(args.input_dir.path());
+  if let Some(part) = args.part {
+    command.args(["--part", &part.to_string()]);
+  }
+  if !args.real {
+    command.arg("--examples-only");
+  }
+  command
+}
+
+/// Rebuilds and checks the day, printing what changed. Returns the records to compare the next run
+/// against, which are kept from `previous` if the build or check failed.
+fn check(args: &WatchArgs, previous: Vec<RunRecord>) -> Vec<RunRecord> {
+  let output = match check_command(args).output() {
+    Ok(output) => output,
+    Err(err) => {
+      println!("Couldn't run cargo: {err}");
+      return previous;
+    }
+  };
+
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  if let Ok(records) = report::parse_json(&stdout) {
+    print!("{}", render_changes(&previous, &records));
+    records
+  } else {
+    // Nothing was reported, most likely because the day didn't compile
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    println!("Check failed:\n{}", stderr.trim_end());
+    previous
+  }
+}
+
+/// Checks the day every time its source or inputs change. Never returns.
+pub fn watch(args: &WatchArgs) -> ! {
+  let files = [Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/day{}.rs", args.day))];
+  let dirs = [args.input_dir.path().join(format!("day{}", args.day))];
+  let interval = Duration::from_millis(args.interval);
+
+  println!(
+    "Watching day{} (src/day{}.rs and {}), Ctrl-C to stop",
+    args.day,
+    args.day,
+    dirs[0].display()
+  );
+
+  let mut last = snapshot(&files, &dirs);
+  let mut records = check(args, Vec::new());
+  loop {
+    thread::sleep(interval);
+
+    let current = snapshot(&files, &dirs);
+    if current != last {
+      println!("\nChange detected, checking day{} again", args.day);
+      last = current;
+      records = check(args, records);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::error::SolveError;
+  use crate::temp_dir::TempDir;
+
+  fn record(part: u8, input: &str, expected: Option<&str>, actual: &str) -> RunRecord {
+    RunRecord::new(
+      1,
+      part,
+      input,
+      expected,
+      &Ok(actual.to_string()),
+      Duration::ZERO,
+      Duration::ZERO,
+    )
+  }
+
+  #[test]
+  fn test_render_changes() {
+    let before = vec![
+      record(1, "input/day1/test1.txt", Some("11"), "10"),
+      record(2, "input/day1/test1.txt", Some("31"), "31"),
+    ];
+    let after = vec![
+      record(1, "input/day1/test1.txt", Some("11"), "11"),
+      record(1, "input/day1/part1.txt", None, "1882714"),
+      RunRecord::new(
+        1,
+        2,
+        "input/day1/test1.txt",
+        Some("31"),
+        &Err(SolveError::NotImplemented),
+        Duration::ZERO,
+        Duration::ZERO,
+      ),
+    ];
+
+    assert_eq!(
+      render_changes(&[], &before),
+      "Part 1 test1.txt: FAIL: expected 11, got 10\nPart 2 test1.txt: PASS\n"
+    );
+    assert_eq!(
+      render_changes(&before, &after),
+      "Part 1 test1.txt: PASS (was FAIL: expected 11, got 10)\n\
+       Part 1 part1.txt: 1882714 (new)\n\
+       Part 2 test1.txt: SKIP: not implemented (was PASS)\n"
+    );
+  }
+
+  #[test]
+  fn test_snapshot() {
+    let dir = TempDir::new("watch");
+    let dirs = [dir.to_path_buf()];
+    let missing = dir.join("missing.rs");
+
+    let before = snapshot(std::slice::from_ref(&missing), &dirs);
+    assert!(before.is_empty());
+
+    fs::write(dir.join("test1.txt"), "1").unwrap();
+    let after = snapshot(std::slice::from_ref(&missing), &dirs);
+    assert_eq!(
+      after.keys().collect::<Vec<_>>(),
+      vec![&dir.join("test1.txt")]
+    );
+  }
+}